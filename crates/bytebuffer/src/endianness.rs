@@ -0,0 +1,20 @@
+/// The byte order used when reading or writing multi-byte values (`u16`, `f32`, etc.) to a buffer
+///
+/// Single bytes and individual bits are unaffected by the byte order.
+///
+/// Defaults to [`Endianness::Big`], which is the byte order every buffer used prior to this being configurable
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Endianness {
+    #[default]
+    Big,
+    Little,
+}
+
+impl Endianness {
+    /// The native byte order of the target platform
+    pub const NATIVE: Self = if cfg!(target_endian = "little") {
+        Self::Little
+    } else {
+        Self::Big
+    };
+}
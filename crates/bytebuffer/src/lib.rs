@@ -1,7 +1,11 @@
+mod endianness;
+mod packing;
 mod read;
 mod write;
 
 pub mod prelude {
+    pub use crate::endianness::*;
+    pub use crate::packing::*;
     pub use crate::read::*;
     pub use crate::write::*;
 }
@@ -0,0 +1,14 @@
+/// How bit writes and byte level writes share the underlying bytes of a buffer
+///
+/// Defaults to [`Packing::Aligned`], which is how every buffer behaved prior to this being configurable
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Packing {
+    /// Byte level reads and writes (`u8`, `u16`, byte slices, etc.) start at the next whole byte,
+    /// skipping the rest of a partially read or written byte
+    #[default]
+    Aligned,
+    /// Every value is packed directly after the previous one, across byte boundaries.
+    ///
+    /// Use `align` to explicitly skip to the next whole byte
+    Bitstream,
+}
@@ -1,11 +1,22 @@
-use crate::read::read_macro::read_owned_ty;
-use crate::read::{BBReadResult, ReadByteBufferRefMut, ReadByteBufferSlice};
+use crate::endianness::Endianness;
+use crate::packing::Packing;
+use crate::read::bb_ref_mut::BudgetRef;
+use crate::read::read_macro::{navigate_in_memory, read_via_ref_mut};
+use crate::read::{
+    BBReadResult, DecodeBudget, DecodeLimits, ReadByteBuffer, ReadByteBufferRefMut,
+    ReadByteBufferSlice,
+};
 use crate::write::WriteByteBufferOwned;
 
 pub struct ReadByteBufferOwned {
     buf: Vec<u8>,
     index: usize,
     bit_index: usize,
+    endianness: Endianness,
+    packing: Packing,
+    /// Holds bytes which had to be reassembled because they weren't byte aligned
+    scratch: Vec<u8>,
+    budget: DecodeBudget,
 }
 
 impl ReadByteBufferOwned {
@@ -17,67 +28,52 @@ impl ReadByteBufferOwned {
             buf: vec.into(),
             index: 0,
             bit_index: 0,
+            endianness: Endianness::default(),
+            packing: Packing::default(),
+            scratch: Vec::new(),
+            budget: DecodeBudget::default(),
         }
     }
 
+    /// Sets the byte order used to read multi-byte values from this buffer
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Sets how bits and bytes are packed together in this buffer
+    pub fn with_packing(mut self, packing: Packing) -> Self {
+        self.packing = packing;
+        self
+    }
+
+    /// Sets the resources decoding from this buffer may use, see [`DecodeLimits`]
+    pub fn with_decode_limits(mut self, limits: DecodeLimits) -> Self {
+        self.budget = DecodeBudget::new(limits);
+        self
+    }
+
     pub fn rbb_ref_mut(&mut self) -> ReadByteBufferRefMut<'_> {
         ReadByteBufferRefMut {
             buf: &self.buf,
             index: &mut self.index,
             bit_index: &mut self.bit_index,
-        }
-    }
-
-    pub fn peek(&self) -> ReadByteBufferSlice<'_> {
-        ReadByteBufferSlice {
-            buf: &self.buf,
-            index: self.index,
-            bit_index: self.bit_index,
+            endianness: self.endianness,
+            packing: self.packing,
+            scratch: Vec::new(),
+            budget: BudgetRef::Borrowed(&mut self.budget),
         }
     }
 
     /// Reads `size` bytes to a vector.
-    /// If you would like to avoid the extra overhead of a vec, use [`ReadByteBufferOwned::rbb_ref_mut`]
-    /// to obtain a [`ReadByteBufferRefMut`], on which you can call [`ReadByteBufferRefMut::read_bytes`]
-
+    /// If you would like to avoid the extra overhead of a vec, use [`ReadByteBuffer::read_bytes`]
     pub fn read_bytes_to_vec(&mut self, size: usize) -> BBReadResult<Vec<u8>> {
-        let mut rbb_ref = self.rbb_ref_mut();
-        let bytes = rbb_ref.read_bytes(size)?;
+        let bytes = self.read_bytes(size)?;
 
         Ok(bytes.to_vec())
     }
 
-    /// Shorthand for [`ReadByteBufferRefMut::read_bits`]
-
-    pub fn read_bits(&mut self, count: usize) -> BBReadResult<u64> {
-        self.rbb_ref_mut().read_bits(count)
-    }
-
-    read_owned_ty!(u8, read_bit);
-    read_owned_ty!(bool, read_bool);
-
-    read_owned_ty!((usize, u8), read_remaining_bits);
-
-    read_owned_ty!(u8, read_u8);
-    read_owned_ty!(u16, read_u16);
-    read_owned_ty!(u32, read_u32);
-    read_owned_ty!(u64, read_u64);
-    read_owned_ty!(u128, read_u128);
-
-    read_owned_ty!(i8, read_i8);
-    read_owned_ty!(i16, read_i16);
-    read_owned_ty!(i32, read_i32);
-    read_owned_ty!(i64, read_i64);
-    read_owned_ty!(i128, read_i128);
-
-    read_owned_ty!(f32, read_f32);
-    read_owned_ty!(f64, read_f64);
-
-    /// Shorthand for [`ReadByteBufferRefMut::flush_bits`]
-
-    pub fn flush_bits(&mut self) {
-        self.rbb_ref_mut().flush_bits();
-    }
+    navigate_in_memory!();
 
     pub fn buf(&self) -> &Vec<u8> {
         &self.buf
@@ -90,6 +86,22 @@ impl ReadByteBufferOwned {
     }
 }
 
+impl ReadByteBuffer for ReadByteBufferOwned {
+    read_via_ref_mut!();
+
+    fn peek(&self) -> ReadByteBufferSlice<'_> {
+        ReadByteBufferSlice {
+            buf: &self.buf,
+            index: self.index,
+            bit_index: self.bit_index,
+            endianness: self.endianness,
+            packing: self.packing,
+            scratch: Vec::new(),
+            budget: self.budget,
+        }
+    }
+}
+
 impl From<WriteByteBufferOwned> for ReadByteBufferOwned {
     fn from(value: WriteByteBufferOwned) -> Self {
         Self::from_vec(value)
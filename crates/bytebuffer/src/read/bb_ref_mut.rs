@@ -1,11 +1,47 @@
-use crate::read::read_macro::read_ref_ty;
-use crate::read::{BBReadResult, ReadByteBufferSlice, ReadError, SpecificError, WithParent};
-use std::ops::Index;
+use crate::endianness::Endianness;
+use crate::packing::Packing;
+use crate::read::{
+    BBReadResult, DecodeBudget, DecodeLimits, ReadByteBuffer, ReadByteBufferSlice, ReadCheckpoint,
+    ReadError, ReadPosition, SpecificError,
+};
+use std::ops::{Deref, DerefMut, Index};
 
 pub struct ReadByteBufferRefMut<'a> {
     pub(super) buf: &'a [u8],
     pub(super) index: &'a mut usize,
     pub(super) bit_index: &'a mut usize,
+    pub(super) endianness: Endianness,
+    pub(super) packing: Packing,
+    /// Holds bytes which had to be reassembled because they weren't byte aligned
+    pub(super) scratch: Vec<u8>,
+    pub(super) budget: BudgetRef<'a>,
+}
+
+/// The [`DecodeBudget`] of a [`ReadByteBufferRefMut`]. It's borrowed from the reader the ref was made from,
+/// so anything claimed through the ref is charged to that reader too
+pub(super) enum BudgetRef<'a> {
+    Owned(DecodeBudget),
+    Borrowed(&'a mut DecodeBudget),
+}
+
+impl Deref for BudgetRef<'_> {
+    type Target = DecodeBudget;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Owned(budget) => budget,
+            Self::Borrowed(budget) => budget,
+        }
+    }
+}
+
+impl DerefMut for BudgetRef<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Owned(budget) => budget,
+            Self::Borrowed(budget) => budget,
+        }
+    }
 }
 
 impl<'a> ReadByteBufferRefMut<'a> {
@@ -14,12 +50,116 @@ impl<'a> ReadByteBufferRefMut<'a> {
             buf,
             index,
             bit_index,
+            endianness: Endianness::default(),
+            packing: Packing::default(),
+            scratch: Vec::new(),
+            budget: BudgetRef::Owned(DecodeBudget::default()),
+        }
+    }
+
+    /// Sets the byte order used to read multi-byte values from this buffer
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Sets how bits and bytes are packed together in this buffer
+    pub fn with_packing(mut self, packing: Packing) -> Self {
+        self.packing = packing;
+        self
+    }
+
+    /// Sets the resources decoding from this buffer may use, see [`DecodeLimits`]
+    ///
+    /// This also sets the limits of the reader the ref was made from
+    pub fn with_decode_limits(mut self, limits: DecodeLimits) -> Self {
+        *self.budget = DecodeBudget::new(limits);
+        self
+    }
+
+    /// The number of whole bytes left to read, a partially read byte is not counted
+    pub fn remaining(&self) -> usize {
+        let partial = (*self.bit_index != 0) as usize;
+
+        self.buf.len().saturating_sub(*self.index + partial)
+    }
+
+    /// Whether every byte has been read
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Moves the read head to the start of the byte at `position`
+    ///
+    /// Errors if `position` is past the end of the buffer
+    pub fn seek(&mut self, position: usize) -> BBReadResult<()> {
+        if position > self.buf.len() {
+            return Err(ReadError::new(
+                SpecificError::Bytes {
+                    remaining_bytes: self.buf.len() as u32,
+                    got: position as u32,
+                },
+                "Seek",
+                None,
+            )
+            .at(self.checkpoint()));
         }
+
+        *self.index = position;
+        *self.bit_index = 0;
+
+        Ok(())
+    }
+
+    /// Whether `remaining` whole bytes can be read, a partially read byte is skipped by byte reads so it isn't counted
+    pub(super) fn has_bytes_remaining(&self, remaining: usize) -> (bool, usize) {
+        let rem = self.remaining();
+        (rem >= remaining, rem)
+    }
+
+    /// Same as [`ReadByteBuffer::read_bytes`], except the returned bytes borrow from the underlying buffer rather than from `self`
+    ///
+    /// Errors if the bytes aren't byte aligned in [`Packing::Bitstream`] mode, as they can't be borrowed
+    pub fn read_borrowed_bytes(&mut self, size: usize) -> BBReadResult<&'a [u8]> {
+        if self.packing == Packing::Bitstream && *self.bit_index != 0 {
+            return Err(ReadError::new(
+                SpecificError::Other("Bytes are not byte aligned".into()),
+                "Read Borrowed Bytes",
+                None,
+            )
+            .at(self.checkpoint()));
+        }
+
+        let (has_enough_bytes, remaining_bytes) = self.has_bytes_remaining(size);
+
+        if !has_enough_bytes {
+            return Err(ReadError::new(
+                SpecificError::Bytes {
+                    remaining_bytes: remaining_bytes as u32,
+                    got: size as u32,
+                },
+                "Read Bytes",
+                None,
+            )
+            .at(self.checkpoint()));
+        }
+
+        self.flush_bits();
+        let index = *self.index;
+
+        *self.index += size;
+
+        let slice = &self.buf[index..(index + size)];
+        Ok(slice)
     }
+}
 
-    pub fn read_bit(&mut self) -> BBReadResult<u8> {
+impl ReadByteBuffer for ReadByteBufferRefMut<'_> {
+    fn read_bit(&mut self) -> BBReadResult<u8> {
         if *self.index >= self.buf.len() {
-            return Err(ReadError::new(SpecificError::SingleBit, "Single Bit", None));
+            return Err(
+                ReadError::new(SpecificError::SingleBit, "Single Bit", None).at(self.checkpoint())
+            );
         }
 
         let bit = self.buf.index(*self.index) >> (7 - *self.bit_index) & 1;
@@ -33,49 +173,28 @@ impl<'a> ReadByteBufferRefMut<'a> {
         Ok(bit)
     }
 
-    /// Resets the bit index to 0 and moves the head over to the next available byte
-
-    pub fn flush_bits(&mut self) {
+    fn flush_bits(&mut self) {
         if *self.bit_index != 0 {
             *self.index += 1;
         }
         *self.bit_index = 0;
     }
 
-    pub fn read_bits(&mut self, count: usize) -> BBReadResult<u64> {
-        assert!(
-            count > 0 && count <= 64,
-            "The maximum bits to read must be between 1 and 64 inclusively [1, 64]; Got: {count}"
-        );
-
-        let mut bits = 0;
-
-        for i in 0..count {
-            let bit = self.read_bit()? as u64;
-            let shifted = bit << (count - i);
-
-            bits |= shifted;
-        }
-
-        Ok(bits)
-    }
-
-    /// Effectively does the same as [`Self::flush_bits`], except it returns the bits that were flushed and that number of bits
-
-    pub fn read_remaining_bits(&mut self) -> BBReadResult<(usize, u8)> {
+    fn read_remaining_bits(&mut self) -> BBReadResult<(usize, u8)> {
         const REM_BITS_STR: &str = "Remaining Bits";
         if *self.bit_index == 8 {
-            return Err(ReadError::new(
-                SpecificError::RemainingBits,
-                REM_BITS_STR,
-                None,
-            ));
+            return Err(
+                ReadError::new(SpecificError::RemainingBits, REM_BITS_STR, None)
+                    .at(self.checkpoint()),
+            );
         }
 
         let read_bits = if let Some(read_bits) = self.buf.get(*self.index) {
             read_bits
         } else {
-            return Err(ReadError::new(SpecificError::SingleBit, REM_BITS_STR, None));
+            return Err(
+                ReadError::new(SpecificError::SingleBit, REM_BITS_STR, None).at(self.checkpoint())
+            );
         };
 
         let bits_read = 8 - *self.bit_index;
@@ -89,72 +208,128 @@ impl<'a> ReadByteBufferRefMut<'a> {
         Ok((bits_read, bits))
     }
 
-    pub fn read_bool(&mut self) -> BBReadResult<bool> {
-        Ok(self.read_bit()? == 1)
-    }
+    fn read_bytes(&mut self, size: usize) -> BBReadResult<&[u8]> {
+        if self.packing == Packing::Bitstream && *self.bit_index != 0 {
+            read_unaligned_bytes(
+                self.buf,
+                self.index,
+                *self.bit_index,
+                size,
+                &mut self.scratch,
+            )?;
+
+            return Ok(&self.scratch);
+        }
 
-    pub fn read_bytes(&mut self, size: usize) -> BBReadResult<&[u8]> {
-        let (has_enough_bytes, remaining_bytes) = self.has_bytes_remaining(size);
+        self.read_borrowed_bytes(size)
+    }
 
-        if !has_enough_bytes {
-            return Err(ReadError::new(
-                SpecificError::Bytes {
-                    remaining_bytes: remaining_bytes as u32,
-                    got: size as u32,
-                },
-                "Read Bytes",
-                None,
-            ));
-        }
+    fn read_u8(&mut self) -> BBReadResult<u8> {
+        if self.packing == Packing::Bitstream && *self.bit_index != 0 {
+            let shift = *self.bit_index;
 
-        self.flush_bits();
-        let index = *self.index;
+            let (Some(high), Some(low)) =
+                (self.buf.get(*self.index), self.buf.get(*self.index + 1))
+            else {
+                return Err(ReadError::new(SpecificError::U8, "u8", None).at(self.checkpoint()));
+            };
 
-        *self.index += size;
+            *self.index += 1;
 
-        let slice = &self.buf[index..(index + size)];
-        Ok(slice)
-    }
+            return Ok((high << shift) | (low >> (8 - shift)));
+        }
 
-    pub fn read_u8(&mut self) -> BBReadResult<u8> {
         self.flush_bits();
         let byte = *self
             .buf
             .get(*self.index)
-            .ok_or_else(|| ReadError::new(SpecificError::U8, "u8", None))?;
+            .ok_or_else(|| ReadError::new(SpecificError::U8, "u8", None).at(self.checkpoint()))?;
 
         *self.index += 1;
 
         Ok(byte)
     }
 
-    pub fn read_i8(&mut self) -> BBReadResult<i8> {
-        self.read_u8().map(|u| u as i8).with_parent("i8")
-    }
-
-    pub fn peek(&self) -> ReadByteBufferSlice<'_> {
+    fn peek(&self) -> ReadByteBufferSlice<'_> {
         ReadByteBufferSlice {
             buf: self.buf,
             index: *self.index,
             bit_index: *self.bit_index,
+            endianness: self.endianness,
+            packing: self.packing,
+            scratch: Vec::new(),
+            budget: *self.budget,
         }
     }
 
-    read_ref_ty!(u16, read_u16, 2);
-    read_ref_ty!(u32, read_u32, 4);
-    read_ref_ty!(u64, read_u64, 8);
-    read_ref_ty!(u128, read_u128, 16);
+    fn endianness(&self) -> Endianness {
+        self.endianness
+    }
 
-    read_ref_ty!(i16, read_i16, 2);
-    read_ref_ty!(i32, read_i32, 4);
-    read_ref_ty!(i64, read_i64, 8);
-    read_ref_ty!(i128, read_i128, 16);
+    fn packing(&self) -> Packing {
+        self.packing
+    }
 
-    read_ref_ty!(f32, read_f32, 4);
-    read_ref_ty!(f64, read_f64, 8);
+    fn position(&self) -> usize {
+        *self.index
+    }
 
-    pub(super) fn has_bytes_remaining(&self, remaining: usize) -> (bool, usize) {
-        let rem = self.buf.len() - *self.index;
-        (rem >= remaining, rem)
+    fn checkpoint(&self) -> ReadCheckpoint {
+        ReadCheckpoint {
+            index: *self.index,
+            bit_index: *self.bit_index,
+        }
+    }
+
+    fn restore(&mut self, checkpoint: ReadCheckpoint) {
+        *self.index = checkpoint.index;
+        *self.bit_index = checkpoint.bit_index;
+    }
+
+    fn decode_budget(&self) -> &DecodeBudget {
+        &self.budget
+    }
+
+    fn decode_budget_mut(&mut self) -> &mut DecodeBudget {
+        &mut self.budget
+    }
+}
+
+/// Reads `size` bytes starting `bit_index` bits into the byte at `index` into `scratch`, by shifting
+/// each pair of neighbouring bytes together. `index` is moved forward by `size`, `bit_index` is unchanged
+pub(super) fn read_unaligned_bytes(
+    buf: &[u8],
+    index: &mut usize,
+    bit_index: usize,
+    size: usize,
+    scratch: &mut Vec<u8>,
+) -> BBReadResult<()> {
+    // The last byte is only partially read, so one more byte than `size` is needed
+    let remaining_bytes = buf.len().saturating_sub(*index + 1);
+
+    if remaining_bytes < size {
+        return Err(ReadError::new(
+            SpecificError::Bytes {
+                remaining_bytes: remaining_bytes as u32,
+                got: size as u32,
+            },
+            "Read Bytes",
+            None,
+        )
+        .at(ReadPosition {
+            byte: *index,
+            bit: bit_index,
+        }));
     }
+
+    scratch.clear();
+    scratch.extend(
+        buf[*index..=(*index + size)]
+            .windows(2)
+            .map(|pair| (pair[0] << bit_index) | (pair[1] >> (8 - bit_index))),
+    );
+
+    *index += size;
+
+    Ok(())
 }
@@ -1,9 +1,18 @@
-use crate::read::ReadByteBufferRefMut;
+use crate::endianness::Endianness;
+use crate::packing::Packing;
+use crate::read::bb_ref_mut::BudgetRef;
+use crate::read::read_macro::{navigate_in_memory, read_via_ref_mut};
+use crate::read::{BBReadResult, DecodeBudget, DecodeLimits, ReadByteBuffer, ReadByteBufferRefMut};
 
 pub struct ReadByteBufferSlice<'a> {
     pub(super) buf: &'a [u8],
     pub(super) index: usize,
     pub(super) bit_index: usize,
+    pub(super) endianness: Endianness,
+    pub(super) packing: Packing,
+    /// Holds bytes which had to be reassembled because they weren't byte aligned
+    pub(super) scratch: Vec<u8>,
+    pub(super) budget: DecodeBudget,
 }
 
 impl<'a> ReadByteBufferSlice<'a> {
@@ -12,14 +21,70 @@ impl<'a> ReadByteBufferSlice<'a> {
             buf,
             index: 0,
             bit_index: 0,
+            endianness: Endianness::default(),
+            packing: Packing::default(),
+            scratch: Vec::new(),
+            budget: DecodeBudget::default(),
         }
     }
 
+    /// Sets the byte order used to read multi-byte values from this buffer
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Sets how bits and bytes are packed together in this buffer
+    pub fn with_packing(mut self, packing: Packing) -> Self {
+        self.packing = packing;
+        self
+    }
+
+    /// Sets the resources decoding from this buffer may use, see [`DecodeLimits`]
+    pub fn with_decode_limits(mut self, limits: DecodeLimits) -> Self {
+        self.budget = DecodeBudget::new(limits);
+        self
+    }
+
+    navigate_in_memory!();
+
+    /// Same as [`ReadByteBuffer::read_bytes`], except the returned bytes borrow from the underlying slice rather than from `self`
+    ///
+    /// Errors if the bytes aren't byte aligned in [`Packing::Bitstream`] mode, as they can't be borrowed
+    pub fn read_borrowed_bytes(&mut self, size: usize) -> BBReadResult<&'a [u8]> {
+        self.rbb_ref_mut().read_borrowed_bytes(size)?;
+
+        let buf = self.buf;
+        let end = self.index;
+
+        Ok(&buf[(end - size)..end])
+    }
+
     pub fn rbb_ref_mut(&mut self) -> ReadByteBufferRefMut<'_> {
         ReadByteBufferRefMut {
-            buf: &self.buf,
+            buf: self.buf,
             index: &mut self.index,
             bit_index: &mut self.bit_index,
+            endianness: self.endianness,
+            packing: self.packing,
+            scratch: Vec::new(),
+            budget: BudgetRef::Borrowed(&mut self.budget),
+        }
+    }
+}
+
+impl ReadByteBuffer for ReadByteBufferSlice<'_> {
+    read_via_ref_mut!();
+
+    fn peek(&self) -> ReadByteBufferSlice<'_> {
+        ReadByteBufferSlice {
+            buf: self.buf,
+            index: self.index,
+            bit_index: self.bit_index,
+            endianness: self.endianness,
+            packing: self.packing,
+            scratch: Vec::new(),
+            budget: self.budget,
         }
     }
 }
@@ -0,0 +1,296 @@
+use crate::endianness::Endianness;
+use crate::packing::Packing;
+use crate::read::bb_ref_mut::read_unaligned_bytes;
+use crate::read::{
+    BBReadResult, DecodeBudget, DecodeLimits, ReadByteBuffer, ReadByteBufferSlice, ReadCheckpoint,
+    ReadError, SpecificError,
+};
+use std::io::{ErrorKind, Read};
+
+/// The most bytes [`ReadByteBufferStream`] allocates ahead of them being read from the source
+const FILL_CHUNK: usize = 8 * 1024;
+
+/// A reader which pulls bytes from an [`Read`] source as they are needed
+///
+/// Only the bytes required by each read are requested from the source, so no data past the end of
+/// the value being decoded is consumed. This makes many small reads on the source, so wrapping
+/// unbuffered sources (files, sockets) in an [`std::io::BufReader`] is recommended.
+///
+/// Bytes pulled from the source are kept until they are [released](ReadByteBuffer::release), so the
+/// reader can only be [restored](ReadByteBuffer::restore) to checkpoints taken since the last release.
+/// Decoding consecutive values with `ReadSerBytes::read_all` releases between values, otherwise every
+/// byte read is held on to.
+pub struct ReadByteBufferStream<R> {
+    reader: R,
+    buf: Vec<u8>,
+    /// The position in the source of the first byte held in `buf`
+    base: usize,
+    index: usize,
+    bit_index: usize,
+    endianness: Endianness,
+    packing: Packing,
+    /// Holds bytes which had to be reassembled because they weren't byte aligned
+    scratch: Vec<u8>,
+    budget: DecodeBudget,
+}
+
+impl<R> ReadByteBufferStream<R>
+where
+    R: Read,
+{
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+            base: 0,
+            index: 0,
+            bit_index: 0,
+            endianness: Endianness::default(),
+            packing: Packing::default(),
+            scratch: Vec::new(),
+            budget: DecodeBudget::default(),
+        }
+    }
+
+    /// Sets the byte order used to read multi-byte values from this buffer
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Sets how bits and bytes are packed together in this buffer
+    pub fn with_packing(mut self, packing: Packing) -> Self {
+        self.packing = packing;
+        self
+    }
+
+    /// Sets the resources decoding from this buffer may use, see [`DecodeLimits`]
+    pub fn with_decode_limits(mut self, limits: DecodeLimits) -> Self {
+        self.budget = DecodeBudget::new(limits);
+        self
+    }
+
+    /// Returns the underlying source.
+    ///
+    /// If a byte is partially read through bit reads, the remaining bits of that byte are lost
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Ensures at least `size` unread bytes are held in the buffer, pulling the missing bytes from the source
+    ///
+    /// The buffer grows in chunks as bytes arrive, so a huge `size` read from untrusted input can't
+    /// allocate more than the source actually holds
+    fn fill(&mut self, size: usize) -> BBReadResult<()> {
+        let eof = |this: &Self, filled: usize| {
+            ReadError::new(
+                SpecificError::Bytes {
+                    remaining_bytes: filled.saturating_sub(this.index) as u32,
+                    got: size as u32,
+                },
+                "Read Stream",
+                None,
+            )
+            .at(this.checkpoint())
+        };
+
+        let Some(required) = self.index.checked_add(size) else {
+            return Err(eof(self, self.buf.len()));
+        };
+
+        let mut filled = self.buf.len();
+
+        while filled < required {
+            let chunk_end = required.min(filled.saturating_add(FILL_CHUNK));
+            self.buf.resize(chunk_end, 0);
+
+            match self.reader.read(&mut self.buf[filled..]) {
+                Ok(0) => {
+                    self.buf.truncate(filled);
+
+                    return Err(eof(self, filled));
+                }
+
+                Ok(read) => filled += read,
+
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+
+                Err(err) => {
+                    self.buf.truncate(filled);
+
+                    return Err(ReadError::from(err).at(self.checkpoint()));
+                }
+            }
+
+            self.buf.truncate(filled);
+        }
+
+        Ok(())
+    }
+
+    /// Replaces running out of bytes with `specific_error`, other errors from the source are kept as is
+    fn eof_as(&self, err: ReadError, specific_error: SpecificError, of: &'static str) -> ReadError {
+        if !err.specific_error.is_eof() {
+            return err;
+        }
+
+        ReadError::new(specific_error, of, None).at(self.checkpoint())
+    }
+}
+
+impl<R> ReadByteBuffer for ReadByteBufferStream<R>
+where
+    R: Read,
+{
+    fn read_bit(&mut self) -> BBReadResult<u8> {
+        self.fill(1)
+            .map_err(|err| self.eof_as(err, SpecificError::SingleBit, "Single Bit"))?;
+
+        let bit = self.buf[self.index] >> (7 - self.bit_index) & 1;
+
+        self.bit_index += 1;
+
+        if self.bit_index == 8 {
+            self.flush_bits();
+        }
+
+        Ok(bit)
+    }
+
+    fn flush_bits(&mut self) {
+        if self.bit_index != 0 {
+            self.index += 1;
+        }
+        self.bit_index = 0;
+    }
+
+    fn read_remaining_bits(&mut self) -> BBReadResult<(usize, u8)> {
+        const REM_BITS_STR: &str = "Remaining Bits";
+
+        self.fill(1)
+            .map_err(|err| self.eof_as(err, SpecificError::SingleBit, REM_BITS_STR))?;
+
+        let bits_read = 8 - self.bit_index;
+
+        let mask = 0xFF >> self.bit_index;
+
+        let bits = self.buf[self.index] & mask;
+
+        self.flush_bits();
+
+        Ok((bits_read, bits))
+    }
+
+    fn read_bytes(&mut self, size: usize) -> BBReadResult<&[u8]> {
+        if self.packing == Packing::Bitstream && self.bit_index != 0 {
+            // The last byte is only partially read, so one more byte than `size` is needed
+            self.fill(size.saturating_add(1))?;
+
+            read_unaligned_bytes(
+                &self.buf,
+                &mut self.index,
+                self.bit_index,
+                size,
+                &mut self.scratch,
+            )?;
+
+            return Ok(&self.scratch);
+        }
+
+        self.flush_bits();
+        self.fill(size)?;
+
+        let index = self.index;
+
+        self.index += size;
+
+        Ok(&self.buf[index..(index + size)])
+    }
+
+    fn read_u8(&mut self) -> BBReadResult<u8> {
+        if self.packing == Packing::Bitstream && self.bit_index != 0 {
+            return match self.read_bytes(1) {
+                Ok(bytes) => Ok(bytes[0]),
+                Err(err) => Err(self.eof_as(err, SpecificError::U8, "u8")),
+            };
+        }
+
+        self.flush_bits();
+        self.fill(1)
+            .map_err(|err| self.eof_as(err, SpecificError::U8, "u8"))?;
+
+        let byte = self.buf[self.index];
+
+        self.index += 1;
+
+        Ok(byte)
+    }
+
+    fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    fn packing(&self) -> Packing {
+        self.packing
+    }
+
+    fn position(&self) -> usize {
+        self.base + self.index
+    }
+
+    fn checkpoint(&self) -> ReadCheckpoint {
+        ReadCheckpoint {
+            index: self.base + self.index,
+            bit_index: self.bit_index,
+        }
+    }
+
+    /// Panics if the checkpoint was taken before the last [release](ReadByteBuffer::release)
+    fn restore(&mut self, checkpoint: ReadCheckpoint) {
+        assert!(
+            checkpoint.index >= self.base,
+            "Can't restore to a checkpoint taken before the stream was released"
+        );
+
+        self.index = checkpoint.index - self.base;
+        self.bit_index = checkpoint.bit_index;
+    }
+
+    /// Drops the bytes before the read head, a partially read byte is kept
+    fn release(&mut self) {
+        self.buf.drain(..self.index);
+        self.base += self.index;
+        self.index = 0;
+    }
+
+    fn decode_budget(&self) -> &DecodeBudget {
+        &self.budget
+    }
+
+    fn decode_budget_mut(&mut self) -> &mut DecodeBudget {
+        &mut self.budget
+    }
+
+    fn at_end(&mut self) -> BBReadResult<bool> {
+        let partial = (self.bit_index != 0) as usize;
+
+        match self.fill(partial + 1) {
+            Ok(()) => Ok(false),
+            Err(err) if err.specific_error.is_eof() => Ok(true),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Only the bytes that have already been pulled from the source are visible through the slice
+    fn peek(&self) -> ReadByteBufferSlice<'_> {
+        ReadByteBufferSlice {
+            buf: &self.buf,
+            index: self.index,
+            bit_index: self.bit_index,
+            endianness: self.endianness,
+            packing: self.packing,
+            scratch: Vec::new(),
+            budget: self.budget,
+        }
+    }
+}
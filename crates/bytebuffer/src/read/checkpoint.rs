@@ -0,0 +1,20 @@
+/// A read position captured by [`crate::prelude::ReadByteBuffer::checkpoint`], including the bit position within the current byte
+///
+/// Only meaningful for the reader it was taken from
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ReadCheckpoint {
+    pub(super) index: usize,
+    pub(super) bit_index: usize,
+}
+
+impl ReadCheckpoint {
+    /// The index of the byte which was under the read head
+    pub fn position(&self) -> usize {
+        self.index
+    }
+
+    /// The number of bits already read from the byte under the read head
+    pub fn bit_position(&self) -> usize {
+        self.bit_index
+    }
+}
@@ -0,0 +1,150 @@
+use crate::read::{BBReadResult, ReadError, SpecificError};
+
+/// Bounds on the resources decoding may use, to safely decode untrusted input
+///
+/// Every limit except the nesting depth is unlimited by default. The depth is limited to
+/// [`DecodeLimits::DEFAULT_MAX_DEPTH`] so deeply nested input can't overflow the stack
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct DecodeLimits {
+    /// The maximum number of bytes collections and strings may allocate in total
+    pub max_alloc: usize,
+    /// The maximum number of items in a single collection
+    pub max_collection_len: usize,
+    /// The maximum length of a single string in bytes
+    pub max_string_len: usize,
+    /// The maximum depth types may be nested within each other
+    pub max_depth: usize,
+}
+
+impl DecodeLimits {
+    pub const UNLIMITED: Self = Self {
+        max_alloc: usize::MAX,
+        max_collection_len: usize::MAX,
+        max_string_len: usize::MAX,
+        max_depth: usize::MAX,
+    };
+
+    pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+    pub fn with_max_alloc(mut self, max_alloc: usize) -> Self {
+        self.max_alloc = max_alloc;
+        self
+    }
+
+    pub fn with_max_collection_len(mut self, max_collection_len: usize) -> Self {
+        self.max_collection_len = max_collection_len;
+        self
+    }
+
+    pub fn with_max_string_len(mut self, max_string_len: usize) -> Self {
+        self.max_string_len = max_string_len;
+        self
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self::UNLIMITED.with_max_depth(Self::DEFAULT_MAX_DEPTH)
+    }
+}
+
+/// The limit which was exceeded, see [`SpecificError::LimitExceeded`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DecodeLimit {
+    Alloc,
+    CollectionLen,
+    StringLen,
+    Depth,
+}
+
+/// The [`DecodeLimits`] of a reader along with how much of them has been used so far
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct DecodeBudget {
+    pub limits: DecodeLimits,
+    /// The number of bytes claimed through [`DecodeBudget::claim_alloc`]
+    pub allocated: usize,
+    /// The current nesting depth
+    pub depth: usize,
+}
+
+impl DecodeBudget {
+    pub fn new(limits: DecodeLimits) -> Self {
+        Self {
+            limits,
+            allocated: 0,
+            depth: 0,
+        }
+    }
+
+    /// Claims `bytes` bytes of the allocation limit
+    pub fn claim_alloc(&mut self, bytes: usize) -> BBReadResult<()> {
+        let allocated = self.allocated.saturating_add(bytes);
+
+        check(DecodeLimit::Alloc, self.limits.max_alloc, allocated)?;
+
+        self.allocated = allocated;
+
+        Ok(())
+    }
+
+    /// Errors if a collection of `len` items is too long, without claiming any allocation
+    pub fn check_collection_len(&self, len: usize) -> BBReadResult<()> {
+        check(
+            DecodeLimit::CollectionLen,
+            self.limits.max_collection_len,
+            len,
+        )
+    }
+
+    /// Errors if a string of `len` bytes is too long, without claiming any allocation
+    pub fn check_string_len(&self, len: usize) -> BBReadResult<()> {
+        check(DecodeLimit::StringLen, self.limits.max_string_len, len)
+    }
+
+    /// Claims the allocation of a collection of `len` items which each take up `item_size` bytes in memory
+    pub fn claim_collection(&mut self, len: usize, item_size: usize) -> BBReadResult<()> {
+        self.check_collection_len(len)?;
+
+        self.claim_alloc(len.saturating_mul(item_size))
+    }
+
+    /// Claims the allocation of a string of `len` bytes
+    pub fn claim_string(&mut self, len: usize) -> BBReadResult<()> {
+        self.check_string_len(len)?;
+
+        self.claim_alloc(len)
+    }
+
+    pub fn enter_nested(&mut self) -> BBReadResult<()> {
+        check(DecodeLimit::Depth, self.limits.max_depth, self.depth + 1)?;
+
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    pub fn exit_nested(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+}
+
+fn check(limit: DecodeLimit, max: usize, got: usize) -> BBReadResult<()> {
+    if got > max {
+        return Err(ReadError::new(
+            SpecificError::LimitExceeded {
+                limit,
+                max: max as u64,
+                got: got as u64,
+            },
+            "Decode limits",
+            None,
+        ));
+    }
+
+    Ok(())
+}
@@ -1,43 +1,273 @@
 mod bb_owned;
 mod bb_ref_mut;
 mod bb_slice;
+mod bb_stream;
+mod checkpoint;
+mod decode_limits;
 mod read_error;
 mod read_macro;
 
 pub use bb_owned::*;
 pub use bb_ref_mut::*;
 pub use bb_slice::*;
+pub use bb_stream::*;
+pub use checkpoint::*;
+pub use decode_limits::*;
 pub use read_error::*;
 
+use crate::endianness::Endianness;
+use crate::packing::Packing;
+use crate::read::read_macro::{read_trait_ty, read_varint_ty, read_zigzag_ty};
+
+/// The read API shared by every reader.
+///
+/// Implementors only need to provide the bit and byte level primitives, multi-byte values are
+/// decoded from [`ReadByteBuffer::read_bytes`] using the reader's [`Endianness`]
 pub trait ReadByteBuffer {
     fn read_bit(&mut self) -> BBReadResult<u8>;
 
     /// Resets the bit index to 0 and moves the head over to the next available byte
-
     fn flush_bits(&mut self);
 
-    fn read_bits(&mut self, count: usize) -> BBReadResult<u8>;
+    /// Moves the read head to the start of the next byte, skipping the rest of a partially read byte.
+    ///
+    /// Same as [`Self::flush_bits`]
+    fn align(&mut self) {
+        self.flush_bits();
+    }
+
+    /// Reads a `count` bit unsigned integer, see [`Self::read_uint`]
+    ///
+    /// Errors if `count` isn't between 1 and 64 inclusively
+    fn read_bits(&mut self, count: usize) -> BBReadResult<u64> {
+        check_bit_count(self, count, 64)?;
+
+        self.read_uint(count).map(|bits| bits as u64)
+    }
+
+    /// Reads a `count` bit unsigned integer, most significant bit first.
+    ///
+    /// The remainder of a partially read byte is read in one go, followed by as many whole bytes as possible,
+    /// so only up to 7 bits on either end are read individually.
+    ///
+    /// Errors if `count` isn't between 1 and 128 inclusively
+    fn read_uint(&mut self, count: usize) -> BBReadResult<u128> {
+        check_bit_count(self, count, 128)?;
+
+        let mut value = 0;
+        let mut remaining = count;
+
+        let bit_index = self.checkpoint().bit_position();
+
+        if bit_index != 0 && remaining >= 8 - bit_index {
+            let (bits_read, bits) = self.read_remaining_bits()?;
+
+            value = bits as u128;
+            remaining -= bits_read;
+        }
+
+        // Either no bits were partially read, or they have been read above, so the head is byte aligned here
+        if remaining >= 8 {
+            for byte in self.read_bytes(remaining / 8)? {
+                value = (value << 8) | *byte as u128;
+            }
+
+            remaining %= 8;
+        }
+
+        for _ in 0..remaining {
+            value = (value << 1) | self.read_bit()? as u128;
+        }
+
+        Ok(value)
+    }
 
-    fn read_remaining_bits(&mut self) -> BBReadResult<u8>;
+    /// Reads a `count` bit two's complement integer, sign extending it to an [`i128`]
+    fn read_int(&mut self, count: usize) -> BBReadResult<i128> {
+        let value = self.read_uint(count)?;
+        let shift = 128 - count;
 
-    fn read_bool(&mut self) -> BBReadResult<bool>;
+        Ok(((value << shift) as i128) >> shift)
+    }
+
+    /// Effectively does the same as [`Self::flush_bits`], except it returns the bits that were flushed and that number of bits
+    fn read_remaining_bits(&mut self) -> BBReadResult<(usize, u8)>;
+
+    fn read_bool(&mut self) -> BBReadResult<bool> {
+        Ok(self.read_bit()? == 1)
+    }
+
+    /// Reads a bool stored as a whole byte, erroring with [`SpecificError::InvalidBool`] if the byte isn't 0 or 1
+    fn read_byte_bool(&mut self) -> BBReadResult<bool> {
+        let checkpoint = self.checkpoint();
+
+        match self.read_u8().with_parent("bool")? {
+            0 => Ok(false),
+            1 => Ok(true),
+            byte => {
+                Err(ReadError::new(SpecificError::InvalidBool(byte), "bool", None).at(checkpoint))
+            }
+        }
+    }
 
     fn read_bytes(&mut self, size: usize) -> BBReadResult<&[u8]>;
 
     fn read_u8(&mut self) -> BBReadResult<u8>;
-    fn read_u16(&mut self) -> BBReadResult<u16>;
-    fn read_u32(&mut self) -> BBReadResult<u32>;
-    fn read_u64(&mut self) -> BBReadResult<u64>;
-    fn read_u128(&mut self) -> BBReadResult<u128>;
 
-    fn read_i8(&mut self) -> BBReadResult<i8>;
-    fn read_i16(&mut self) -> BBReadResult<i16>;
-    fn read_i32(&mut self) -> BBReadResult<i32>;
-    fn read_i64(&mut self) -> BBReadResult<i64>;
-    fn read_i128(&mut self) -> BBReadResult<i128>;
+    fn read_i8(&mut self) -> BBReadResult<i8> {
+        self.read_u8().map(|u| u as i8).with_parent("i8")
+    }
+
+    read_trait_ty!(u16, read_u16, 2);
+    read_trait_ty!(u32, read_u32, 4);
+    read_trait_ty!(u64, read_u64, 8);
+    read_trait_ty!(u128, read_u128, 16);
+
+    read_trait_ty!(i16, read_i16, 2);
+    read_trait_ty!(i32, read_i32, 4);
+    read_trait_ty!(i64, read_i64, 8);
+    read_trait_ty!(i128, read_i128, 16);
 
-    fn read_f32(&mut self) -> BBReadResult<f32>;
-    fn read_f64(&mut self) -> BBReadResult<f64>;
+    read_trait_ty!(f32, read_f32, 4);
+    read_trait_ty!(f64, read_f64, 8);
+
+    read_varint_ty!(u16, read_varint_u16);
+    read_varint_ty!(u32, read_varint_u32);
+    read_varint_ty!(u64, read_varint_u64);
+    read_varint_ty!(u128, read_varint_u128);
+
+    read_zigzag_ty!(i16, read_varint_i16, read_varint_u16);
+    read_zigzag_ty!(i32, read_varint_i32, read_varint_u32);
+    read_zigzag_ty!(i64, read_varint_i64, read_varint_u64);
+    read_zigzag_ty!(i128, read_varint_i128, read_varint_u128);
+
+    /// The byte order used to read multi-byte values
+    fn endianness(&self) -> Endianness;
+
+    /// How bits and bytes are packed together, see [`Packing`]
+    fn packing(&self) -> Packing;
 
     fn peek(&self) -> ReadByteBufferSlice<'_>;
+
+    /// The index of the byte currently under the read head
+    fn position(&self) -> usize;
+
+    /// Captures the current read position so it can later be returned to with [`Self::restore`]
+    fn checkpoint(&self) -> ReadCheckpoint;
+
+    /// Moves the read head to a position captured by [`Self::checkpoint`]
+    fn restore(&mut self, checkpoint: ReadCheckpoint);
+
+    /// Allows the reader to drop the bytes before the read head, which streams otherwise keep so they can be restored.
+    ///
+    /// Checkpoints taken before this call may no longer be restored
+    fn release(&mut self) {}
+
+    /// The [`DecodeLimits`] of this reader and how much of them has been used
+    fn decode_budget(&self) -> &DecodeBudget;
+
+    fn decode_budget_mut(&mut self) -> &mut DecodeBudget;
+
+    /// Errors if a collection of `len` items, each taking up `item_size` bytes in memory, would exceed the [`DecodeLimits`]
+    ///
+    /// Must be called before allocating the collection
+    fn claim_collection(&mut self, len: usize, item_size: usize) -> BBReadResult<()> {
+        let checkpoint = self.checkpoint();
+
+        self.decode_budget_mut()
+            .claim_collection(len, item_size)
+            .map_err(|err| err.at(checkpoint))
+    }
+
+    /// Errors if a string of `len` bytes would exceed the [`DecodeLimits`]
+    ///
+    /// Must be called before allocating the string
+    fn claim_string(&mut self, len: usize) -> BBReadResult<()> {
+        let checkpoint = self.checkpoint();
+
+        self.decode_budget_mut()
+            .claim_string(len)
+            .map_err(|err| err.at(checkpoint))
+    }
+
+    /// Enters a nested type, erroring if it is nested deeper than the [`DecodeLimits`] allow.
+    ///
+    /// Must be followed by [`Self::exit_nested`] once the nested type is decoded, even if decoding it failed
+    fn enter_nested(&mut self) -> BBReadResult<()> {
+        let checkpoint = self.checkpoint();
+
+        self.decode_budget_mut()
+            .enter_nested()
+            .map_err(|err| err.at(checkpoint))
+    }
+
+    fn exit_nested(&mut self) {
+        self.decode_budget_mut().exit_nested();
+    }
+
+    /// Whether every whole byte has been read, a partially read byte is not counted
+    ///
+    /// Streams pull a byte from their source to find out, erroring only if the source fails
+    fn at_end(&mut self) -> BBReadResult<bool> {
+        Ok(self.peek().is_empty())
+    }
+
+    /// Errors with [`SpecificError::TrailingBytes`] if any whole bytes are left to read.
+    ///
+    /// Streams only count the bytes already pulled from their source
+    fn ensure_consumed(&mut self) -> BBReadResult<()> {
+        if self.at_end()? {
+            return Ok(());
+        }
+
+        let remaining = self.peek().remaining();
+
+        Err(ReadError::new(
+            SpecificError::TrailingBytes(remaining as u32),
+            "Trailing bytes",
+            None,
+        )
+        .at(self.checkpoint()))
+    }
+
+    /// Moves the read head forward by `count` bytes, discarding any partially read byte first
+    fn skip(&mut self, count: usize) -> BBReadResult<()> {
+        self.read_bytes(count)?;
+
+        Ok(())
+    }
+
+    /// Reads `len` bytes and returns a reader bounded to them, using the same byte order as this reader
+    ///
+    /// The sub reader starts with this reader's [`DecodeBudget`], what it uses isn't claimed from this reader
+    fn sub_reader(&mut self, len: usize) -> BBReadResult<ReadByteBufferSlice<'_>> {
+        let endianness = self.endianness();
+        let packing = self.packing();
+        let budget = *self.decode_budget();
+        let bytes = self.read_bytes(len)?;
+
+        let mut sub_reader = ReadByteBufferSlice::new(bytes)
+            .with_endianness(endianness)
+            .with_packing(packing);
+
+        *sub_reader.decode_budget_mut() = budget;
+
+        Ok(sub_reader)
+    }
+}
+
+fn check_bit_count<R>(buf: &R, count: usize, max: u8) -> BBReadResult<()>
+where
+    R: ReadByteBuffer + ?Sized,
+{
+    if count == 0 || count > max as usize {
+        return Err(ReadError::new(
+            SpecificError::InvalidBitCount { max, got: count },
+            "Bit count",
+            None,
+        )
+        .at(buf.checkpoint()));
+    }
+
+    Ok(())
 }
@@ -1,80 +1,228 @@
+use crate::read::{DecodeLimit, ReadCheckpoint};
 use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::io;
-use std::io::ErrorKind;
+use std::sync::Arc;
 
-pub type BBReadResult<T> = Result<T, ReadError<'static>>;
+pub type BBReadResult<T> = Result<T, ReadError>;
 
 /// An error that represents an inability to read or deserialize a type in some shape or form
-///
-/// Most of the time you can get away with a static lifetime here, it only exists for future proofing and custom implementations
-
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct ReadError<'s> {
+pub struct ReadError {
     /// The specific error generated from being deserialized, this is the value of the individual bytebuffer fail
     ///
     /// For example when reading a [String] first a [u16] (we'll call x) is read then based on the resulting number, that many bytes are read.
     /// If reading the x number of bytes fails then the [`SpecificError`] will be [`SpecificError::Bytes`]
-    pub specific_error: SpecificError<'s>,
+    pub specific_error: SpecificError,
     /// The full type name which is being deserialized
     ///
     /// For example "String"
-    pub of: Cow<'s, str>,
+    pub of: Cow<'static, str>,
     /// If the value being deserialized is a subset of another
     ///
     /// For example elements of type S in a `Vec<S>`
     /// As in, if a `Vec<S>` fails to be deserialized, this field should be `Some` with the read error of S
     pub child: Option<Box<Self>>,
+    /// Where in the buffer the error occurred, `None` if it wasn't recorded
+    pub position: Option<ReadPosition>,
+    /// Where in the value being read the error occurred, see [`WithPath`]
+    pub path: ErrorPath,
 }
 
-impl<'a> ReadError<'a> {
+impl ReadError {
     pub fn new(
-        specific_error: SpecificError<'a>,
-        of: impl Into<Cow<'a, str>>,
+        specific_error: SpecificError,
+        of: impl Into<Cow<'static, str>>,
         child: Option<Self>,
     ) -> Self {
+        let position = child.as_ref().and_then(|child| child.position);
+        let path = child
+            .as_ref()
+            .map(|child| child.path.clone())
+            .unwrap_or_default();
+
         Self {
             specific_error,
             of: of.into(),
-            child: child.map(|child| Box::new(child)),
+            child: child.map(Box::new),
+            position,
+            path,
         }
     }
 
-    pub fn new_parent(self, of: impl Into<Cow<'a, str>>) -> Self {
+    /// Wraps an error which doesn't originate from this crate, see [`SpecificError::Custom`]
+    pub fn custom(
+        err: impl Into<Box<dyn Error + Send + Sync>>,
+        of: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self::new(SpecificError::Custom(Arc::from(err.into())), of, None)
+    }
+
+    pub fn new_parent(self, of: impl Into<Cow<'static, str>>) -> Self {
         Self::new(self.specific_error.clone(), of.into(), Some(self))
     }
+
+    /// Records where the error occurred, unless a more precise position was already recorded
+    pub fn at(mut self, position: impl Into<ReadPosition>) -> Self {
+        self.position.get_or_insert(position.into());
+        self
+    }
+
+    /// Moves the recorded positions of this error and its children `bytes` bytes forward, used when
+    /// an error originates from a reader over only part of the buffer
+    pub fn offset_position(mut self, bytes: usize) -> Self {
+        let mut error = Some(&mut self);
+
+        while let Some(current) = error {
+            if let Some(position) = &mut current.position {
+                position.byte += bytes;
+            }
+
+            error = current.child.as_deref_mut();
+        }
+
+        self
+    }
 }
 
-impl<'a> Display for ReadError<'a> {
+/// Renders as `path: error at position`, i.e. `Config.players[3].inventory: unexpected EOF at byte 412`.
+///
+/// The type name is used in place of the path if none was recorded
+impl Display for ReadError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Error reading type: {:?} of {}",
-            self.specific_error, self.of,
-        )?;
-
-        if let Some(child) = &self.child {
-            write!(f, "; Error originates in child:\n{}", child)?;
+        if self.path.is_empty() {
+            write!(f, "{}", self.of)?;
+        } else {
+            write!(f, "{}", self.path)?;
+        }
+
+        write!(f, ": {}", self.specific_error)?;
+
+        if let Some(position) = &self.position {
+            write!(f, " at {position}")?;
         }
 
         Ok(())
     }
 }
 
-pub trait WithParent<'a> {
-    fn with_parent(self, of: impl Into<Cow<'a, str>>) -> Self;
+pub trait WithParent {
+    fn with_parent(self, of: impl Into<Cow<'static, str>>) -> Self;
 }
 
-impl<'a, T> WithParent<'a> for Result<T, ReadError<'a>> {
-    fn with_parent(self, of: impl Into<Cow<'a, str>>) -> Self {
+impl<T> WithParent for Result<T, ReadError> {
+    fn with_parent(self, of: impl Into<Cow<'static, str>>) -> Self {
         self.map_err(|read_error| read_error.new_parent(of))
     }
 }
 
+/// Records where in a value an error occurred, as it's passed out of the value.
+///
+/// Each segment is added in front of the path, so a type's name is only kept while it's the outermost
+/// value; once the error passes into the field or element holding that type, the field or element replaces it
+pub trait WithPath {
+    fn in_type(self, name: impl Into<Cow<'static, str>>) -> Self;
+
+    fn in_field(self, name: impl Into<Cow<'static, str>>) -> Self;
+
+    fn in_variant(self, name: impl Into<Cow<'static, str>>) -> Self;
+
+    fn at_index(self, index: usize) -> Self;
+}
+
+impl WithPath for ReadError {
+    fn in_type(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.path.push_front(PathSegment::Type(name.into()));
+        self
+    }
+
+    fn in_field(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.path.push_front(PathSegment::Field(name.into()));
+        self
+    }
+
+    fn in_variant(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.path.push_front(PathSegment::Variant(name.into()));
+        self
+    }
+
+    fn at_index(mut self, index: usize) -> Self {
+        self.path.push_front(PathSegment::Index(index));
+        self
+    }
+}
+
+impl<T> WithPath for Result<T, ReadError> {
+    fn in_type(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.map_err(|read_error| read_error.in_type(name))
+    }
+
+    fn in_field(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.map_err(|read_error| read_error.in_field(name))
+    }
+
+    fn in_variant(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.map_err(|read_error| read_error.in_variant(name))
+    }
+
+    fn at_index(self, index: usize) -> Self {
+        self.map_err(|read_error| read_error.at_index(index))
+    }
+}
+
+/// Where in a value a [`ReadError`] occurred, from the outermost value inwards
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct ErrorPath {
+    pub segments: Vec<PathSegment>,
+}
+
+impl ErrorPath {
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Adds `segment` in front of the path, replacing the name of the type which was outermost until now
+    pub fn push_front(&mut self, segment: PathSegment) {
+        if let Some(PathSegment::Type(_)) = self.segments.first() {
+            self.segments.remove(0);
+        }
+
+        self.segments.insert(0, segment);
+    }
+}
+
+impl Display for ErrorPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Type(name) => write!(f, "{name}")?,
+                PathSegment::Field(name) if i == 0 => write!(f, "{name}")?,
+                PathSegment::Field(name) => write!(f, ".{name}")?,
+                PathSegment::Variant(name) => write!(f, "::{name}")?,
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum PathSegment {
+    /// The name of the outermost type
+    Type(Cow<'static, str>),
+    /// A struct or variant field, tuple fields are named by their index
+    Field(Cow<'static, str>),
+    /// The variant of an enum
+    Variant(Cow<'static, str>),
+    /// An element of a collection
+    Index(usize),
+}
+
 const DEFAULT_STR: &str = "Default";
 
-impl Default for ReadError<'static> {
+impl Default for ReadError {
     fn default() -> Self {
         Self::new(
             SpecificError::Other(Cow::Borrowed(DEFAULT_STR)),
@@ -84,30 +232,67 @@ impl Default for ReadError<'static> {
     }
 }
 
-impl Error for ReadError<'static> {
+impl Error for ReadError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         Some(&self.specific_error)
     }
 }
 
-impl From<ReadError<'static>> for io::Error {
-    fn from(value: ReadError<'static>) -> Self {
-        io::Error::new(ErrorKind::Other, Box::new(value))
+impl From<ReadError> for io::Error {
+    fn from(value: ReadError) -> Self {
+        let kind = if value.specific_error.is_eof() {
+            io::ErrorKind::UnexpectedEof
+        } else {
+            io::ErrorKind::InvalidData
+        };
+
+        io::Error::new(kind, value)
     }
 }
 
-impl<'a> From<io::Error> for ReadError<'a> {
+impl From<io::Error> for ReadError {
     fn from(value: io::Error) -> Self {
-        ReadError::new(
-            SpecificError::Other(value.to_string().into()),
-            "io::Error",
-            None,
-        )
+        let specific_error = match value.kind() {
+            io::ErrorKind::UnexpectedEof => SpecificError::UnexpectedEof,
+            _ => SpecificError::Custom(Arc::new(value)),
+        };
+
+        ReadError::new(specific_error, "io::Error", None)
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub enum SpecificError<'s> {
+/// The position of the read head when a [`ReadError`] occurred
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ReadPosition {
+    /// The index of the byte under the read head
+    pub byte: usize,
+    /// The number of bits already read from that byte
+    pub bit: usize,
+}
+
+impl From<ReadCheckpoint> for ReadPosition {
+    fn from(value: ReadCheckpoint) -> Self {
+        Self {
+            byte: value.position(),
+            bit: value.bit_position(),
+        }
+    }
+}
+
+impl Display for ReadPosition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "byte {}", self.byte)?;
+
+        if self.bit != 0 {
+            write!(f, " bit {}", self.bit)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SpecificError {
     U8,
     Bytes {
         remaining_bytes: u32,
@@ -115,40 +300,197 @@ pub enum SpecificError<'s> {
     },
     SingleBit,
     RemainingBits,
+    /// Wide enough for any enum tag, including `u16`, varint and bit-packed tags
     EnumOrdinalOutOfBounds {
-        max_bound: u8,
-        got: u8,
+        max_bound: u64,
+        got: u64,
     },
     /// Error produced when deriving an invalid for serialization (an empty enum)
     InvalidEnum,
-    Other(Cow<'s, str>),
+    /// A variable length integer was longer than the integer type being read
+    VarintOverflow,
+    /// Decoding would exceed one of the reader's [`DecodeLimits`](crate::read::DecodeLimits)
+    LimitExceeded {
+        limit: DecodeLimit,
+        max: u64,
+        got: u64,
+    },
+    /// A string's bytes weren't valid utf8
+    InvalidUtf8,
+    /// An io source reported [`io::ErrorKind::UnexpectedEof`].
+    ///
+    /// Readers running out of data report the read that failed instead, i.e. [`Self::U8`] or [`Self::Bytes`],
+    /// use [`Self::is_eof`] to check for any of them
+    UnexpectedEof,
+    /// A decoded length was too large to be a `usize` on this platform
+    LengthLimit,
+    /// A number of bits outside of `1..=max` was requested from a bit level read
+    InvalidBitCount {
+        max: u8,
+        got: usize,
+    },
+    /// A bool stored as a whole byte was neither 0 nor 1
+    InvalidBool(u8),
+    /// Bytes were left over after the value was read, when the whole buffer was expected to be used
+    TrailingBytes(u32),
+    /// A value was read without using any of the input, so reading values until the input ends would never finish
+    NoProgress,
+    /// The data was written by a newer version of a versioned type than the one reading it
+    UnknownVersion {
+        current: u32,
+        got: u32,
+    },
+    /// An error which doesn't originate from this crate, i.e. from a custom implementation or an io source.
+    ///
+    /// Compared by identity, two `Custom` errors are only equal when they share the same error
+    Custom(Arc<dyn Error + Send + Sync>),
+    Other(Cow<'static, str>),
+}
+
+impl SpecificError {
+    /// Whether the error was caused by running out of data, this is the supported way to check for the end of the input
+    pub fn is_eof(&self) -> bool {
+        matches!(
+            self,
+            Self::U8
+                | Self::Bytes { .. }
+                | Self::SingleBit
+                | Self::RemainingBits
+                | Self::UnexpectedEof
+        )
+    }
 }
 
-impl<'a> Display for SpecificError<'a> {
+impl PartialEq for SpecificError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Bytes {
+                    remaining_bytes: l_remaining,
+                    got: l_got,
+                },
+                Self::Bytes {
+                    remaining_bytes: r_remaining,
+                    got: r_got,
+                },
+            ) => l_remaining == r_remaining && l_got == r_got,
+            (
+                Self::EnumOrdinalOutOfBounds {
+                    max_bound: l_max,
+                    got: l_got,
+                },
+                Self::EnumOrdinalOutOfBounds {
+                    max_bound: r_max,
+                    got: r_got,
+                },
+            ) => l_max == r_max && l_got == r_got,
+            (
+                Self::LimitExceeded {
+                    limit: l_limit,
+                    max: l_max,
+                    got: l_got,
+                },
+                Self::LimitExceeded {
+                    limit: r_limit,
+                    max: r_max,
+                    got: r_got,
+                },
+            ) => l_limit == r_limit && l_max == r_max && l_got == r_got,
+            (
+                Self::InvalidBitCount {
+                    max: l_max,
+                    got: l_got,
+                },
+                Self::InvalidBitCount {
+                    max: r_max,
+                    got: r_got,
+                },
+            ) => l_max == r_max && l_got == r_got,
+            (
+                Self::UnknownVersion {
+                    current: l_current,
+                    got: l_got,
+                },
+                Self::UnknownVersion {
+                    current: r_current,
+                    got: r_got,
+                },
+            ) => l_current == r_current && l_got == r_got,
+            (Self::InvalidBool(l), Self::InvalidBool(r)) => l == r,
+            (Self::TrailingBytes(l), Self::TrailingBytes(r)) => l == r,
+            (Self::Custom(l), Self::Custom(r)) => Arc::ptr_eq(l, r),
+            (Self::Other(l), Self::Other(r)) => l == r,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for SpecificError {}
+
+impl Display for SpecificError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let s = match self {
-            Self::U8 => "U8",
+        match self {
+            Self::U8 => write!(f, "unexpected EOF reading a byte"),
 
             Self::Bytes {
                 remaining_bytes,
                 got,
-            } => &format!("Bytes, remaining: {}; got: {}", remaining_bytes, got),
+            } => write!(
+                f,
+                "unexpected EOF reading {got} bytes, {remaining_bytes} remaining"
+            ),
 
-            Self::SingleBit => "SingleBit",
+            Self::SingleBit => write!(f, "unexpected EOF reading a bit"),
 
-            Self::RemainingBits => "RemainingBits",
+            Self::RemainingBits => write!(f, "unexpected EOF reading the remaining bits"),
 
             Self::EnumOrdinalOutOfBounds { got, max_bound } => {
-                &format!("EnumBounds, max bound expected: {max_bound}; got: {got}")
+                write!(
+                    f,
+                    "enum ordinal {got} is out of bounds, the max is {max_bound}"
+                )
             }
 
-            Self::InvalidEnum => "InvalidEnum",
+            Self::InvalidEnum => write!(f, "enum has no variants"),
 
-            Self::Other(other) => &format!("Other: {}", other),
-        };
+            Self::VarintOverflow => write!(f, "varint overflows its integer type"),
+
+            Self::LimitExceeded { limit, max, got } => {
+                write!(f, "{limit:?} limit of {max} exceeded, got {got}")
+            }
+
+            Self::InvalidUtf8 => write!(f, "invalid utf8"),
+
+            Self::UnexpectedEof => write!(f, "unexpected EOF"),
 
-        write!(f, "SpecificError::{}", s)
+            Self::LengthLimit => write!(f, "length is too large to be a usize"),
+
+            Self::InvalidBitCount { max, got } => {
+                write!(f, "can't read {got} bits, expected 1 to {max}")
+            }
+
+            Self::InvalidBool(got) => write!(f, "invalid bool {got}"),
+
+            Self::TrailingBytes(remaining) => write!(f, "{remaining} trailing bytes"),
+
+            Self::NoProgress => write!(f, "value was read without using any input"),
+
+            Self::UnknownVersion { current, got } => {
+                write!(f, "unknown version {got}, the current version is {current}")
+            }
+
+            Self::Custom(err) => write!(f, "{err}"),
+
+            Self::Other(other) => write!(f, "{other}"),
+        }
     }
 }
 
-impl<'a> Error for SpecificError<'a> {}
+impl Error for SpecificError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Custom(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
@@ -1,26 +1,179 @@
-macro_rules! read_ref_ty {
+macro_rules! read_trait_ty {
     ($t: ty, $call: ident, $size: literal) => {
         #[doc = concat!("Attempts to read a ", stringify!($t), " from the buffer")]
         #[doc = concat!("If there are not at least ", stringify!($size), " bytes in the buffer, it will return Err")]
-        pub fn $call(&mut self) -> crate::prelude::BBReadResult<$t> {
+        fn $call(&mut self) -> crate::prelude::BBReadResult<$t> {
             use byteorder::ByteOrder;
 
-            let bytes = crate::prelude::WithParent::with_parent(crate::prelude::ReadByteBufferRefMut::read_bytes(self, $size), stringify!($t))?;
+            let endianness = self.endianness();
+            let bytes = crate::prelude::WithParent::with_parent(self.read_bytes($size), stringify!($t))?;
 
-            Ok(byteorder::BigEndian::$call(bytes))
+            let n = match endianness {
+                crate::prelude::Endianness::Big => byteorder::BigEndian::$call(bytes),
+                crate::prelude::Endianness::Little => byteorder::LittleEndian::$call(bytes),
+            };
+
+            Ok(n)
         }
     };
 }
 
-macro_rules! read_owned_ty {
+macro_rules! read_varint_ty {
     ($t: ty, $call: ident) => {
-        #[doc = concat!("Shorthand for [`ReadByteBufferRefMut::", stringify!($call), "`]")]
-        pub fn $call(&mut self) -> crate::prelude::BBReadResult<$t> {
-            crate::prelude::ReadByteBufferRefMut::$call(
-                &mut crate::prelude::ReadByteBufferOwned::rbb_ref_mut(self), /* usize */
-            )
+        #[doc = concat!("Reads a LEB128 encoded ", stringify!($t))]
+        #[doc = ""]
+        #[doc = concat!("Errors if the encoded value doesn't fit in a ", stringify!($t))]
+        fn $call(&mut self) -> crate::prelude::BBReadResult<$t> {
+            const OF: &str = concat!("varint ", stringify!($t));
+
+            let start = self.checkpoint();
+            let mut value: $t = 0;
+            let mut shift = 0;
+
+            loop {
+                let byte = crate::prelude::WithParent::with_parent(self.read_u8(), OF)?;
+                let bits = (byte & 0x7F) as $t;
+
+                if shift >= <$t>::BITS || bits.leading_zeros() < shift {
+                    return Err(crate::prelude::ReadError::new(
+                        crate::prelude::SpecificError::VarintOverflow,
+                        OF,
+                        None,
+                    )
+                    .at(start));
+                }
+
+                value |= bits << shift;
+
+                if byte & 0x80 == 0 {
+                    return Ok(value);
+                }
+
+                shift += 7;
+            }
+        }
+    };
+}
+
+macro_rules! read_zigzag_ty {
+    ($t: ty, $call: ident, $unsigned_call: ident) => {
+        #[doc = concat!("Reads a ZigZag and LEB128 encoded ", stringify!($t))]
+        fn $call(&mut self) -> crate::prelude::BBReadResult<$t> {
+            let n = self.$unsigned_call()?;
+
+            Ok((n >> 1) as $t ^ -((n & 1) as $t))
+        }
+    };
+}
+
+/// Implements the [`crate::prelude::ReadByteBuffer`] primitives of a reader by delegating to its `rbb_ref_mut`
+macro_rules! read_via_ref_mut {
+    () => {
+        fn read_bit(&mut self) -> crate::prelude::BBReadResult<u8> {
+            self.rbb_ref_mut().read_bit()
+        }
+
+        fn flush_bits(&mut self) {
+            self.rbb_ref_mut().flush_bits();
+        }
+
+        fn read_remaining_bits(&mut self) -> crate::prelude::BBReadResult<(usize, u8)> {
+            self.rbb_ref_mut().read_remaining_bits()
+        }
+
+        fn read_bytes(&mut self, size: usize) -> crate::prelude::BBReadResult<&[u8]> {
+            if self.packing == crate::prelude::Packing::Bitstream && self.bit_index != 0 {
+                crate::read::bb_ref_mut::read_unaligned_bytes(
+                    &self.buf,
+                    &mut self.index,
+                    self.bit_index,
+                    size,
+                    &mut self.scratch,
+                )?;
+
+                return Ok(&self.scratch);
+            }
+
+            self.rbb_ref_mut().read_borrowed_bytes(size)
+        }
+
+        fn read_u8(&mut self) -> crate::prelude::BBReadResult<u8> {
+            self.rbb_ref_mut().read_u8()
+        }
+
+        fn endianness(&self) -> crate::prelude::Endianness {
+            self.endianness
+        }
+
+        fn packing(&self) -> crate::prelude::Packing {
+            self.packing
+        }
+
+        fn position(&self) -> usize {
+            self.index
+        }
+
+        fn checkpoint(&self) -> crate::prelude::ReadCheckpoint {
+            crate::prelude::ReadCheckpoint {
+                index: self.index,
+                bit_index: self.bit_index,
+            }
+        }
+
+        fn restore(&mut self, checkpoint: crate::prelude::ReadCheckpoint) {
+            self.index = checkpoint.index;
+            self.bit_index = checkpoint.bit_index;
+        }
+
+        fn decode_budget(&self) -> &crate::prelude::DecodeBudget {
+            &self.budget
+        }
+
+        fn decode_budget_mut(&mut self) -> &mut crate::prelude::DecodeBudget {
+            &mut self.budget
+        }
+    };
+}
+
+/// Implements the navigation of a reader which holds the entire buffer in memory
+macro_rules! navigate_in_memory {
+    () => {
+        /// The number of whole bytes left to read, a partially read byte is not counted
+        pub fn remaining(&self) -> usize {
+            let partial = (self.bit_index != 0) as usize;
+
+            self.buf.len().saturating_sub(self.index + partial)
+        }
+
+        /// Whether every byte has been read
+        pub fn is_empty(&self) -> bool {
+            self.remaining() == 0
+        }
+
+        /// Moves the read head to the start of the byte at `position`
+        ///
+        /// Errors if `position` is past the end of the buffer
+        pub fn seek(&mut self, position: usize) -> crate::prelude::BBReadResult<()> {
+            if position > self.buf.len() {
+                return Err(crate::prelude::ReadError::new(
+                    crate::prelude::SpecificError::Bytes {
+                        remaining_bytes: self.buf.len() as u32,
+                        got: position as u32,
+                    },
+                    "Seek",
+                    None,
+                )
+                .at(crate::prelude::ReadByteBuffer::checkpoint(self)));
+            }
+
+            self.index = position;
+            self.bit_index = 0;
+
+            Ok(())
         }
     };
 }
 
-pub(crate) use {read_owned_ty, read_ref_ty};
+pub(crate) use {
+    navigate_in_memory, read_trait_ty, read_varint_ty, read_via_ref_mut, read_zigzag_ty,
+};
@@ -1,5 +1,145 @@
 mod bb_ref;
 mod owned;
+mod slice;
+mod stream;
+mod write_error;
 mod write_macro;
 
 pub use owned::*;
+pub use slice::*;
+pub use stream::*;
+pub use write_error::*;
+
+use crate::endianness::Endianness;
+use crate::packing::Packing;
+use crate::write::write_macro::{write_trait_ty, write_varint_ty, write_zigzag_ty};
+use std::io;
+
+/// The write API shared by every writer.
+///
+/// Implementors only need to provide the bit and byte level primitives, multi-byte values are
+/// encoded using the writer's [`Endianness`] and written through [`WriteByteBuffer::write_bytes`]
+pub trait WriteByteBuffer {
+    fn write_bit(&mut self, bit: u8);
+
+    /// Writes the lowest `count` bits of `bits`, see [`Self::write_uint`]
+    ///
+    /// Errors with [`WriteError::InvalidBitCount`] if `count` isn't between 1 and 64
+    fn write_bits(&mut self, bits: u64, count: usize) -> BBWriteResult<()> {
+        check_bit_count(64, count)?;
+
+        self.write_uint(bits as u128, count)
+    }
+
+    /// Writes the lowest `count` bits of `value`, most significant bit first. Any higher bits are ignored.
+    ///
+    /// The remainder of a partially written byte is written in one go, followed by as many whole bytes as possible,
+    /// so only up to 7 bits on either end are written individually
+    ///
+    /// Errors with [`WriteError::InvalidBitCount`] if `count` isn't between 1 and 128
+    fn write_uint(&mut self, value: u128, count: usize) -> BBWriteResult<()> {
+        check_bit_count(128, count)?;
+
+        let mut remaining = count;
+
+        let partial_bits = self.partial_bits();
+
+        if partial_bits != 0 && remaining >= 8 - partial_bits {
+            remaining -= 8 - partial_bits;
+
+            self.write_remaining_bits((value >> remaining) as u8)?;
+        }
+
+        // Either no byte was partially written, or it has been filled above, so the writer is byte aligned here
+        if remaining >= 8 {
+            let byte_count = remaining / 8;
+            remaining %= 8;
+
+            let bytes = (value >> remaining).to_be_bytes();
+            self.write_bytes(&bytes[(bytes.len() - byte_count)..]);
+        }
+
+        for i in (0..remaining).rev() {
+            self.write_bit((value >> i) as u8 & 1);
+        }
+
+        Ok(())
+    }
+
+    /// Writes `value` as a `count` bit two's complement integer, see [`Self::write_uint`]
+    fn write_int(&mut self, value: i128, count: usize) -> BBWriteResult<()> {
+        self.write_uint(value as u128, count)
+    }
+
+    /// The number of bits written to the byte currently being written to, 0 if no byte is partially written
+    fn partial_bits(&self) -> usize;
+
+    /// Fills the remaining bits of the byte currently being written to with `bits`
+    ///
+    /// Errors if no byte is partially written
+    fn write_remaining_bits(&mut self, bits: u8) -> io::Result<()>;
+
+    fn write_bool(&mut self, b: bool) {
+        self.write_bit(b as u8);
+    }
+
+    /// Writes a bool as a whole byte, see [`ReadByteBuffer::read_byte_bool`](crate::read::ReadByteBuffer::read_byte_bool)
+    fn write_byte_bool(&mut self, b: bool) {
+        self.write_u8(b as u8);
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]);
+
+    fn write_u8(&mut self, n: u8);
+
+    fn write_i8(&mut self, n: i8) {
+        self.write_u8(n as u8);
+    }
+
+    write_trait_ty!(u16, write_u16, 2);
+    write_trait_ty!(u32, write_u32, 4);
+    write_trait_ty!(u64, write_u64, 8);
+    write_trait_ty!(u128, write_u128, 16);
+
+    write_trait_ty!(i16, write_i16, 2);
+    write_trait_ty!(i32, write_i32, 4);
+    write_trait_ty!(i64, write_i64, 8);
+    write_trait_ty!(i128, write_i128, 16);
+
+    write_trait_ty!(f32, write_f32, 4);
+    write_trait_ty!(f64, write_f64, 8);
+
+    write_varint_ty!(u16, write_varint_u16);
+    write_varint_ty!(u32, write_varint_u32);
+    write_varint_ty!(u64, write_varint_u64);
+    write_varint_ty!(u128, write_varint_u128);
+
+    write_zigzag_ty!(i16, write_varint_i16, write_varint_u16, u16);
+    write_zigzag_ty!(i32, write_varint_i32, write_varint_u32, u32);
+    write_zigzag_ty!(i64, write_varint_i64, write_varint_u64, u64);
+    write_zigzag_ty!(i128, write_varint_i128, write_varint_u128, u128);
+
+    /// The byte order used to write multi-byte values
+    fn endianness(&self) -> Endianness;
+
+    /// How bits and bytes are packed together, see [`Packing`]
+    fn packing(&self) -> Packing;
+
+    /// Moves the writer to the start of the next byte, leaving the unwritten bits of a partially written byte as 0
+    fn align(&mut self);
+
+    /// Hints that at least `additional` more bytes are about to be written.
+    ///
+    /// Writers which don't hold their output in memory can ignore this
+    fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+}
+
+fn check_bit_count(max: u8, count: usize) -> BBWriteResult<()> {
+    if count == 0 || count > max as usize {
+        return Err(WriteError::InvalidBitCount { max, got: count });
+    }
+
+    Ok(())
+}
@@ -1,11 +1,15 @@
-use crate::write::write_macro::write_ty;
-use byteorder::ByteOrder;
+use crate::endianness::Endianness;
+use crate::packing::Packing;
+use crate::write::WriteByteBuffer;
 use std::io;
 use std::io::ErrorKind;
 
+#[derive(Debug, Clone)]
 pub struct WriteByteBufferOwned {
     buf: Vec<u8>,
     bit_pos: usize,
+    endianness: Endianness,
+    packing: Packing,
 }
 
 impl WriteByteBufferOwned {
@@ -17,10 +21,72 @@ impl WriteByteBufferOwned {
         Self {
             buf: Vec::with_capacity(capacity),
             bit_pos: 8,
+            endianness: Endianness::default(),
+            packing: Packing::default(),
         }
     }
 
-    pub fn write_bit(&mut self, bit: u8) {
+    /// Sets the byte order used to write multi-byte values to this buffer
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Sets how bits and bytes are packed together in this buffer
+    pub fn with_packing(mut self, packing: Packing) -> Self {
+        self.packing = packing;
+        self
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.buf
+    }
+
+    pub fn buf(&self) -> &[u8] {
+        &self.buf
+    }
+
+    pub fn buf_mut(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Whether a byte is currently partially written to through bit writes
+    pub(crate) fn has_partial_byte(&self) -> bool {
+        self.bit_pos != 8
+    }
+
+    /// Removes the first `count` bytes of the buffer
+    pub(crate) fn drain_front(&mut self, count: usize) {
+        self.buf.drain(..count);
+    }
+}
+
+impl WriteByteBuffer for WriteByteBufferOwned {
+    fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    fn packing(&self) -> Packing {
+        self.packing
+    }
+
+    fn align(&mut self) {
+        self.bit_pos = 8;
+    }
+
+    fn partial_bits(&self) -> usize {
+        if self.bit_pos == 8 { 0 } else { self.bit_pos }
+    }
+
+    fn write_bit(&mut self, bit: u8) {
         if self.bit_pos == 8 {
             self.write_u8(0);
             self.bit_pos = 0;
@@ -33,17 +99,7 @@ impl WriteByteBufferOwned {
         self.bit_pos += 1;
     }
 
-    pub fn write_bits(&mut self, bits: u64, count: usize) {
-        let mask = 1;
-
-        for i in 0..count {
-            let shifted_right = bits >> count - i;
-            let bit = shifted_right & mask;
-            self.write_bit(bit as u8);
-        }
-    }
-
-    pub fn write_remaining_bits(&mut self, bits: u8) -> io::Result<()> {
+    fn write_remaining_bits(&mut self, bits: u8) -> io::Result<()> {
         if self.bit_pos == 8 {
             return Err(ErrorKind::UnexpectedEof.into());
         }
@@ -63,57 +119,39 @@ impl WriteByteBufferOwned {
         Ok(())
     }
 
-    pub fn write_bool(&mut self, b: bool) {
-        self.write_bit(b as u8);
-    }
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        if self.packing == Packing::Bitstream && self.bit_pos != 8 {
+            let shift = self.bit_pos;
 
-    pub fn write_bytes(&mut self, bytes: &[u8]) {
-        self.buf.extend_from_slice(bytes);
-    }
-
-    pub fn write_u8(&mut self, n: u8) {
-        self.bit_pos = 8;
-
-        self.buf.push(n);
-    }
-
-    pub fn write_i8(&mut self, n: i8) {
-        self.bit_pos = 8;
+            self.buf.reserve(bytes.len());
 
-        self.buf.push(n as u8);
-    }
+            for byte in bytes {
+                let len = self.buf.len();
 
-    write_ty!(u16, write_u16, 2);
-    write_ty!(u32, write_u32, 4);
-    write_ty!(u64, write_u64, 8);
-    write_ty!(u128, write_u128, 16);
+                self.buf[len - 1] |= byte >> shift;
+                self.buf.push(byte << (8 - shift));
+            }
 
-    write_ty!(i16, write_i16, 2);
-    write_ty!(i32, write_i32, 4);
-    write_ty!(i64, write_i64, 8);
-    write_ty!(i128, write_i128, 16);
-
-    write_ty!(f32, write_f32, 4);
-    write_ty!(f64, write_f64, 8);
+            return;
+        }
 
-    pub fn into_vec(self) -> Vec<u8> {
-        self.buf
-    }
+        self.bit_pos = 8;
 
-    pub fn buf(&self) -> &[u8] {
-        &self.buf
+        self.buf.extend_from_slice(bytes);
     }
 
-    pub fn buf_mut(&mut self) -> &mut [u8] {
-        &mut self.buf
+    fn write_u8(&mut self, n: u8) {
+        self.write_bytes(&[n]);
     }
 
-    pub fn len(&self) -> usize {
-        self.buf.len()
+    fn reserve(&mut self, additional: usize) {
+        self.buf.reserve(additional);
     }
+}
 
-    pub fn reserve(&mut self, additional: usize) {
-        self.buf.reserve(additional);
+impl Default for WriteByteBufferOwned {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
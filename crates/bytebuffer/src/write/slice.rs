@@ -0,0 +1,187 @@
+use crate::endianness::Endianness;
+use crate::packing::Packing;
+use crate::write::WriteByteBuffer;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::ErrorKind;
+
+/// A writer over a caller provided, fixed size slice. Never allocates.
+///
+/// Writes can't fail, so once a write doesn't fit in the slice the writer is marked as overflowed and
+/// every following write is discarded. The overflow is returned by [`WriteByteBufferSlice::finish`]
+pub struct WriteByteBufferSlice<'a> {
+    buf: &'a mut [u8],
+    /// The number of bytes written, including those which didn't fit into the slice
+    len: usize,
+    bit_pos: usize,
+    overflowed: bool,
+    endianness: Endianness,
+    packing: Packing,
+}
+
+impl<'a> WriteByteBufferSlice<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self {
+            buf,
+            len: 0,
+            bit_pos: 8,
+            overflowed: false,
+            endianness: Endianness::default(),
+            packing: Packing::default(),
+        }
+    }
+
+    /// Sets the byte order used to write multi-byte values to this buffer
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Sets how bits and bytes are packed together in this buffer
+    pub fn with_packing(mut self, packing: Packing) -> Self {
+        self.packing = packing;
+        self
+    }
+
+    /// The number of bytes the slice can hold
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// The number of bytes written so far, including any which didn't fit into the slice
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether a write has exceeded the capacity of the slice
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    /// The bytes written so far, only the bytes which fit into the slice are included
+    pub fn buf(&self) -> &[u8] {
+        &self.buf[..self.len.min(self.buf.len())]
+    }
+
+    /// Returns the number of bytes written, or an error if they didn't fit into the slice
+    pub fn finish(self) -> Result<usize, SliceOverflowError> {
+        if self.overflowed {
+            return Err(SliceOverflowError {
+                capacity: self.buf.len(),
+                required: self.len,
+            });
+        }
+
+        Ok(self.len)
+    }
+
+    /// Reserves `count` bytes, returning the slice to write them to if they fit
+    fn claim(&mut self, count: usize) -> Option<&mut [u8]> {
+        let start = self.len;
+        self.len += count;
+
+        if self.overflowed || self.len > self.buf.len() {
+            self.overflowed = true;
+            return None;
+        }
+
+        Some(&mut self.buf[start..self.len])
+    }
+}
+
+impl WriteByteBuffer for WriteByteBufferSlice<'_> {
+    fn partial_bits(&self) -> usize {
+        if self.bit_pos == 8 { 0 } else { self.bit_pos }
+    }
+
+    fn write_bit(&mut self, bit: u8) {
+        if self.bit_pos == 8 {
+            self.write_u8(0);
+            self.bit_pos = 0;
+        }
+
+        if !self.overflowed {
+            self.buf[self.len - 1] |= bit << (7 - self.bit_pos);
+        }
+
+        self.bit_pos += 1;
+    }
+
+    fn write_remaining_bits(&mut self, bits: u8) -> io::Result<()> {
+        if self.bit_pos == 8 {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+
+        if !self.overflowed {
+            self.buf[self.len - 1] |= bits & (0xFF >> self.bit_pos);
+        }
+
+        self.bit_pos = 8;
+
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        if self.packing == Packing::Bitstream && self.bit_pos != 8 {
+            let shift = self.bit_pos;
+            let start = self.len;
+
+            if self.claim(bytes.len()).is_some() {
+                for (i, byte) in bytes.iter().enumerate() {
+                    self.buf[start + i - 1] |= byte >> shift;
+                    self.buf[start + i] = byte << (8 - shift);
+                }
+            }
+
+            return;
+        }
+
+        self.bit_pos = 8;
+
+        if let Some(slice) = self.claim(bytes.len()) {
+            slice.copy_from_slice(bytes);
+        }
+    }
+
+    fn write_u8(&mut self, n: u8) {
+        self.write_bytes(&[n]);
+    }
+
+    fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    fn packing(&self) -> Packing {
+        self.packing
+    }
+
+    fn align(&mut self) {
+        self.bit_pos = 8;
+    }
+}
+
+/// Returned when the data written to a [`WriteByteBufferSlice`] doesn't fit into its slice
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SliceOverflowError {
+    /// The size of the slice in bytes
+    pub capacity: usize,
+    /// The number of bytes which were written
+    pub required: usize,
+}
+
+impl Display for SliceOverflowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Slice overflowed: {} bytes were written to a slice of {} bytes",
+            self.required, self.capacity
+        )
+    }
+}
+
+impl Error for SliceOverflowError {}
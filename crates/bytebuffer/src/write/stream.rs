@@ -0,0 +1,179 @@
+use crate::endianness::Endianness;
+use crate::packing::Packing;
+use crate::write::write_macro::write_stream_ty;
+use crate::write::{WriteByteBuffer, WriteByteBufferOwned};
+use std::io;
+use std::io::Write;
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// A writer which buffers output in memory and writes it out to an [`io::Write`] sink once the buffer is full
+///
+/// Writes can't fail, so the first error returned by the sink is stored and every following write is discarded.
+/// The error is returned by [`WriteByteBufferStream::flush`] or [`WriteByteBufferStream::finish`].
+///
+/// [`WriteByteBufferStream::finish`] must be called once everything has been written, otherwise any
+/// buffered data is lost
+pub struct WriteByteBufferStream<W>
+where
+    W: Write,
+{
+    writer: W,
+    buf: WriteByteBufferOwned,
+    capacity: usize,
+    error: Option<io::Error>,
+}
+
+impl<W> WriteByteBufferStream<W>
+where
+    W: Write,
+{
+    pub fn new(writer: W) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, writer)
+    }
+
+    /// Creates a writer which holds up to `capacity` bytes before writing them to the sink
+    pub fn with_capacity(capacity: usize, writer: W) -> Self {
+        Self {
+            writer,
+            buf: WriteByteBufferOwned::with_capacity(capacity),
+            capacity,
+            error: None,
+        }
+    }
+
+    /// Sets the byte order used to write multi-byte values to this buffer
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.buf = self.buf.with_endianness(endianness);
+        self
+    }
+
+    /// Sets how bits and bytes are packed together in this buffer
+    pub fn with_packing(mut self, packing: Packing) -> Self {
+        self.buf = self.buf.with_packing(packing);
+        self
+    }
+
+    /// Writes every complete byte to the sink and flushes it.
+    ///
+    /// A byte which is partially written through bit writes is held back until it is complete
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.write_out(false);
+
+        if self.error.is_none()
+            && let Err(err) = self.writer.flush()
+        {
+            self.error = Some(err);
+        }
+
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes all remaining data to the sink, including any partially written byte, and returns the sink
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_out(true);
+
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+
+    fn write_out(&mut self, include_partial: bool) {
+        let mut count = self.buf.len();
+
+        if !include_partial && self.buf.has_partial_byte() {
+            count -= 1;
+        }
+
+        if count == 0 {
+            return;
+        }
+
+        if self.error.is_none()
+            && let Err(err) = self.writer.write_all(&self.buf.buf()[..count])
+        {
+            self.error = Some(err);
+        }
+
+        self.buf.drain_front(count);
+    }
+
+    fn write_out_if_full(&mut self) {
+        if self.buf.len() >= self.capacity {
+            self.write_out(false);
+        }
+    }
+}
+
+impl<W> WriteByteBuffer for WriteByteBufferStream<W>
+where
+    W: Write,
+{
+    fn partial_bits(&self) -> usize {
+        self.buf.partial_bits()
+    }
+
+    fn write_bit(&mut self, bit: u8) {
+        self.buf.write_bit(bit);
+        self.write_out_if_full();
+    }
+
+    fn write_remaining_bits(&mut self, bits: u8) -> io::Result<()> {
+        self.buf.write_remaining_bits(bits)?;
+        self.write_out_if_full();
+
+        Ok(())
+    }
+
+    /// Byte slices at least as large as the buffer's capacity are written straight to the sink
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        if bytes.len() < self.capacity || self.buf.has_partial_byte() {
+            self.buf.write_bytes(bytes);
+            self.write_out_if_full();
+
+            return;
+        }
+
+        self.write_out(false);
+
+        if self.error.is_none()
+            && let Err(err) = self.writer.write_all(bytes)
+        {
+            self.error = Some(err);
+        }
+    }
+
+    write_stream_ty!(u8, write_u8);
+    write_stream_ty!(u16, write_u16);
+    write_stream_ty!(u32, write_u32);
+    write_stream_ty!(u64, write_u64);
+    write_stream_ty!(u128, write_u128);
+
+    write_stream_ty!(i8, write_i8);
+    write_stream_ty!(i16, write_i16);
+    write_stream_ty!(i32, write_i32);
+    write_stream_ty!(i64, write_i64);
+    write_stream_ty!(i128, write_i128);
+
+    write_stream_ty!(f32, write_f32);
+    write_stream_ty!(f64, write_f64);
+
+    fn endianness(&self) -> Endianness {
+        self.buf.endianness()
+    }
+
+    fn packing(&self) -> Packing {
+        self.buf.packing()
+    }
+
+    fn align(&mut self) {
+        self.buf.align();
+    }
+}
@@ -0,0 +1,88 @@
+use crate::write::SliceOverflowError;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+pub type BBWriteResult<T> = Result<T, WriteError>;
+
+/// An error that represents an inability to serialize or write a type
+#[derive(Debug)]
+pub enum WriteError {
+    /// A length was larger than its length type can hold
+    LengthOverflow {
+        len: usize,
+        /// The name of the type the length was written as
+        length_type: &'static str,
+    },
+    /// An enum variant's id was larger than the enum's tag can hold
+    IdOverflow {
+        id: u64,
+        /// The name of the type the tag is written as
+        tag_type: &'static str,
+    },
+    /// The value can't be serialized in its current state, i.e. an `Err` or a mutably borrowed `RefCell`
+    InvalidState(Cow<'static, str>),
+    /// The data didn't fit into the slice being written to
+    SliceOverflow(SliceOverflowError),
+    /// A number of bits outside of `1..=max` was given to a bit level write
+    InvalidBitCount { max: u8, got: usize },
+    /// The sink being written to failed
+    Io(io::Error),
+}
+
+impl WriteError {
+    pub fn invalid_state(reason: impl Into<Cow<'static, str>>) -> Self {
+        Self::InvalidState(reason.into())
+    }
+}
+
+impl Display for WriteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LengthOverflow { len, length_type } => {
+                write!(f, "Length {len} doesn't fit into a {length_type}")
+            }
+            Self::IdOverflow { id, tag_type } => {
+                write!(f, "Id {id} doesn't fit into a {tag_type} tag")
+            }
+            Self::InvalidState(reason) => write!(f, "Invalid state: {reason}"),
+            Self::SliceOverflow(err) => write!(f, "{err}"),
+            Self::InvalidBitCount { max, got } => {
+                write!(f, "Can't write {got} bits, expected 1 to {max}")
+            }
+            Self::Io(err) => write!(f, "Io error: {err}"),
+        }
+    }
+}
+
+impl Error for WriteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SliceOverflow(err) => Some(err),
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for WriteError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<SliceOverflowError> for WriteError {
+    fn from(value: SliceOverflowError) -> Self {
+        Self::SliceOverflow(value)
+    }
+}
+
+impl From<WriteError> for io::Error {
+    fn from(value: WriteError) -> Self {
+        match value {
+            WriteError::Io(err) => err,
+            err => io::Error::other(err),
+        }
+    }
+}
@@ -1,15 +1,64 @@
-macro_rules! write_ty {
+macro_rules! write_trait_ty {
     ($t: ty, $call: ident, $size: literal) => {
         #[doc = concat!("Writes a ", stringify!($t), " to the buffer")]
-        pub fn $call(&mut self, n: $t) {
-            self.bit_pos = 8;
+        fn $call(&mut self, n: $t) {
+            use byteorder::ByteOrder;
 
             let mut new_slice = [0; $size];
-            byteorder::BigEndian::$call(&mut new_slice, n);
+            match self.endianness() {
+                crate::prelude::Endianness::Big => byteorder::BigEndian::$call(&mut new_slice, n),
+                crate::prelude::Endianness::Little => {
+                    byteorder::LittleEndian::$call(&mut new_slice, n)
+                }
+            }
 
-            self.buf.extend(&new_slice);
+            self.write_bytes(&new_slice);
         }
     };
 }
 
-pub(crate) use write_ty;
+macro_rules! write_varint_ty {
+    ($t: ty, $call: ident) => {
+        #[doc = concat!("Writes a ", stringify!($t), " LEB128 encoded, 7 bits per byte, so smaller values take up fewer bytes")]
+        fn $call(&mut self, mut n: $t) {
+            let mut bytes = [0; (<$t>::BITS as usize).div_ceil(7)];
+            let mut len = 0;
+
+            loop {
+                let byte = (n & 0x7F) as u8;
+                n >>= 7;
+
+                if n == 0 {
+                    bytes[len] = byte;
+                    len += 1;
+                    break;
+                }
+
+                bytes[len] = byte | 0x80;
+                len += 1;
+            }
+
+            self.write_bytes(&bytes[..len]);
+        }
+    };
+}
+
+macro_rules! write_zigzag_ty {
+    ($t: ty, $call: ident, $unsigned_call: ident, $unsigned: ty) => {
+        #[doc = concat!("Writes a ", stringify!($t), " ZigZag and LEB128 encoded, so values closer to 0 take up fewer bytes")]
+        fn $call(&mut self, n: $t) {
+            self.$unsigned_call(((n << 1) ^ (n >> (<$t>::BITS - 1))) as $unsigned);
+        }
+    };
+}
+
+macro_rules! write_stream_ty {
+    ($t: ty, $call: ident) => {
+        fn $call(&mut self, n: $t) {
+            self.buf.$call(n);
+            self.write_out_if_full();
+        }
+    };
+}
+
+pub(crate) use {write_stream_ty, write_trait_ty, write_varint_ty, write_zigzag_ty};
@@ -1,4 +1,9 @@
-use bytebuffer::prelude::{ReadByteBufferOwned, WriteByteBufferOwned};
+use bytebuffer::prelude::{
+    DecodeLimit, DecodeLimits, Endianness, Packing, ReadByteBuffer, ReadByteBufferOwned,
+    ReadByteBufferSlice, ReadByteBufferStream, ReadError, SliceOverflowError, SpecificError,
+    WriteByteBuffer, WriteByteBufferOwned, WriteByteBufferSlice, WriteByteBufferStream, WriteError,
+};
+use std::io::Read;
 
 #[test]
 fn test_individual_bits() {
@@ -98,7 +103,7 @@ fn test_write_bits() {
 
     let bits = 110;
 
-    wbb.write_bits(bits, 7);
+    wbb.write_bits(bits, 7).unwrap();
 
     let mut rbb = ReadByteBufferOwned::from_vec(wbb.into_vec());
 
@@ -114,3 +119,732 @@ fn test_read_end_bit() {
 
     rbb.read_bool().expect_err("Should error and not panic");
 }
+
+#[test]
+fn test_endianness() {
+    let n = 0x0102_0304u32;
+
+    let mut wbb_big = WriteByteBufferOwned::new();
+    wbb_big.write_u32(n);
+
+    let mut wbb_little = WriteByteBufferOwned::new().with_endianness(Endianness::Little);
+    wbb_little.write_u32(n);
+    wbb_little.write_f64(-1.5);
+
+    assert_eq!(wbb_big.buf(), &[1, 2, 3, 4]);
+    assert_eq!(&wbb_little.buf()[..4], &[4, 3, 2, 1]);
+
+    let mut rbb =
+        ReadByteBufferOwned::from_vec(wbb_little.into_vec()).with_endianness(Endianness::Little);
+
+    assert_eq!(rbb.read_u32().expect("Little endian u32 to exist"), n);
+    assert_eq!(rbb.read_f64().expect("Little endian f64 to exist"), -1.5);
+}
+
+#[test]
+fn test_stream() {
+    /// Hands out a single byte per read to force the stream to refill repeatedly
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match (self.0.split_first(), buf.first_mut()) {
+                (Some((byte, rest)), Some(out)) => {
+                    *out = *byte;
+                    self.0 = rest;
+                    Ok(1)
+                }
+
+                _ => Ok(0),
+            }
+        }
+    }
+
+    let mut wbb = WriteByteBufferOwned::new();
+
+    wbb.write_bool(true);
+    wbb.write_bool(false);
+    wbb.write_u32(0xDEADBEEF);
+    wbb.write_bytes(&[9, 8, 7]);
+    wbb.write_i16(-300);
+
+    let v = wbb.into_vec();
+
+    let mut rbb = ReadByteBufferStream::new(Trickle(&v));
+
+    assert!(rbb.read_bool().expect("Bit 1 to exist"));
+    assert!(!rbb.read_bool().expect("Bit 2 to exist"));
+    assert_eq!(rbb.read_u32().expect("u32 to exist"), 0xDEADBEEF);
+    assert_eq!(rbb.read_bytes(3).expect("Bytes to exist"), &[9, 8, 7]);
+    assert_eq!(rbb.read_i16().expect("i16 to exist"), -300);
+
+    rbb.read_u8().expect_err("Stream should be exhausted");
+}
+
+#[test]
+fn test_write_stream() {
+    fn write_all(wbb: &mut dyn WriteByteBuffer) {
+        wbb.write_bool(true);
+        wbb.write_bool(true);
+        wbb.write_u16(513);
+        wbb.write_bytes(&[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        wbb.write_bool(false);
+        wbb.write_bool(true);
+        wbb.write_f32(2.5);
+        wbb.write_bool(true);
+    }
+
+    let mut owned = WriteByteBufferOwned::new();
+    write_all(&mut owned);
+
+    // A capacity smaller than most writes forces the stream to write out often
+    let mut stream = WriteByteBufferStream::with_capacity(3, Vec::new());
+    write_all(&mut stream);
+
+    let streamed = stream.finish().expect("Writing to a vec can't fail");
+
+    assert_eq!(owned.buf(), streamed.as_slice());
+}
+
+#[test]
+fn test_write_stream_error() {
+    #[derive(Debug)]
+    struct Full;
+
+    impl std::io::Write for Full {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::StorageFull.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut stream = WriteByteBufferStream::with_capacity(2, Full);
+
+    stream.write_u64(12);
+    stream.write_u64(34);
+
+    let err = stream
+        .finish()
+        .expect_err("Error from the sink should be surfaced");
+
+    assert_eq!(err.kind(), std::io::ErrorKind::StorageFull);
+}
+
+#[test]
+fn test_write_slice() {
+    fn write_all(wbb: &mut dyn WriteByteBuffer) {
+        wbb.write_bool(true);
+        wbb.write_bool(false);
+        wbb.write_bool(true);
+        wbb.write_u32(0xDEADBEEF);
+        wbb.write_bool(true);
+        wbb.write_remaining_bits(0b0101).unwrap();
+        wbb.write_i16(-4);
+    }
+
+    let mut owned = WriteByteBufferOwned::new().with_endianness(Endianness::Little);
+    write_all(&mut owned);
+
+    // Garbage in the slice must not leak into bit writes
+    let mut slice = [0xFF; 16];
+    let mut wbb = WriteByteBufferSlice::new(&mut slice).with_endianness(Endianness::Little);
+    write_all(&mut wbb);
+
+    assert_eq!(wbb.buf(), owned.buf());
+
+    let len = wbb.finish().expect("Data should fit in the slice");
+
+    assert_eq!(&slice[..len], owned.buf());
+}
+
+#[test]
+fn test_write_slice_overflow() {
+    let mut slice = [0; 5];
+    let mut wbb = WriteByteBufferSlice::new(&mut slice);
+
+    wbb.write_u32(1);
+    assert!(!wbb.overflowed());
+
+    wbb.write_u16(2);
+    wbb.write_u8(3);
+    wbb.write_bool(true);
+
+    assert!(wbb.overflowed());
+    assert_eq!(
+        wbb.finish(),
+        Err(SliceOverflowError {
+            capacity: 5,
+            required: 8,
+        })
+    );
+}
+
+#[test]
+fn test_readers_share_api() {
+    fn read_all(rbb: &mut impl ReadByteBuffer) -> (bool, u8, u16, f64, Vec<u8>) {
+        let flag = rbb.read_bool().expect("Read bool");
+        let byte = rbb.read_u8().expect("Read u8");
+        let short = rbb.read_u16().expect("Read u16");
+        let float = rbb.read_f64().expect("Read f64");
+        let bytes = rbb.read_bytes(3).expect("Read bytes").to_vec();
+
+        (flag, byte, short, float, bytes)
+    }
+
+    let mut wbb = WriteByteBufferOwned::new();
+    wbb.write_bool(true);
+    wbb.write_u8(200);
+    wbb.write_u16(4000);
+    wbb.write_f64(-1.5);
+    wbb.write_bytes(&[7, 8, 9]);
+
+    let bytes = wbb.into_vec();
+    let expected = (true, 200, 4000, -1.5, vec![7, 8, 9]);
+
+    let mut owned = ReadByteBufferOwned::from_vec(bytes.clone());
+    assert_eq!(read_all(&mut owned.peek()), expected);
+    assert_eq!(read_all(&mut owned.rbb_ref_mut()), expected);
+
+    let mut owned = ReadByteBufferOwned::from_vec(bytes.clone());
+    assert_eq!(read_all(&mut owned), expected);
+
+    let mut slice = ReadByteBufferSlice::new(&bytes);
+    assert_eq!(read_all(&mut slice), expected);
+}
+
+#[test]
+fn test_navigation() {
+    let bytes = [0b1010_0000, 1, 2, 3, 4, 5, 6];
+    let mut rbb = ReadByteBufferSlice::new(&bytes);
+
+    assert_eq!(rbb.position(), 0);
+    assert_eq!(rbb.remaining(), 7);
+
+    rbb.read_bool().expect("Read bit");
+    let checkpoint = rbb.checkpoint();
+
+    // The partially read byte isn't counted as remaining
+    assert_eq!(rbb.remaining(), 6);
+    assert_eq!(checkpoint.position(), 0);
+    assert_eq!(checkpoint.bit_position(), 1);
+
+    rbb.skip(2).expect("Skip 2 bytes");
+    assert_eq!(rbb.position(), 3);
+
+    {
+        let mut sub = rbb.sub_reader(2).expect("Sub reader of 2 bytes");
+
+        assert_eq!(sub.read_u16().expect("Read u16 from sub reader"), 0x0304);
+        assert!(sub.is_empty());
+        assert!(sub.read_u8().is_err());
+    }
+
+    assert_eq!(rbb.position(), 5);
+
+    rbb.restore(checkpoint);
+    assert!(!rbb.read_bool().expect("Read second bit"));
+    assert!(rbb.read_bool().expect("Read third bit"));
+
+    rbb.seek(6).expect("Seek to last byte");
+    assert_eq!(rbb.read_u8().expect("Read last byte"), 6);
+    assert!(rbb.is_empty());
+
+    assert!(rbb.seek(8).is_err());
+
+    let mut owned = ReadByteBufferOwned::from_vec(bytes.to_vec());
+    owned.seek(1).expect("Seek owned buffer");
+
+    let mut rbb_ref = owned.rbb_ref_mut();
+    assert_eq!(rbb_ref.remaining(), 6);
+    assert_eq!(rbb_ref.read_u8().expect("Read byte"), 1);
+    assert_eq!(owned.position(), 2);
+}
+
+#[test]
+fn test_stream_restore() {
+    let bytes: &[u8] = &[0, 1, 0, 2, 9];
+    let mut rbb = ReadByteBufferStream::new(bytes);
+
+    let checkpoint = rbb.checkpoint();
+
+    assert_eq!(rbb.read_u32().expect("Read u32"), 0x00010002);
+
+    rbb.restore(checkpoint);
+
+    assert_eq!(rbb.read_u16().expect("Read u16 after restoring"), 1);
+    assert_eq!(rbb.position(), 2);
+
+    rbb.skip(2).expect("Skip buffered bytes");
+
+    // Releasing drops the bytes already read, positions still count from the start of the source
+    rbb.release();
+    assert_eq!(rbb.position(), 4);
+
+    let checkpoint = rbb.checkpoint();
+
+    assert_eq!(rbb.read_u8().expect("Read remaining byte"), 9);
+
+    rbb.restore(checkpoint);
+
+    assert_eq!(rbb.read_u8().expect("Read byte after restoring"), 9);
+}
+
+#[test]
+fn test_bits_layout() {
+    let mut wbb = WriteByteBufferOwned::new();
+
+    wbb.write_bits(0b101, 3).unwrap();
+    wbb.write_bits(u64::MAX, 64).unwrap();
+    wbb.write_bits(0b1, 1).unwrap();
+
+    // Bits are packed most significant bit first, without being shifted out of place
+    assert_eq!(
+        wbb.buf(),
+        &[
+            0b1011_1111,
+            0xFF,
+            0xFF,
+            0xFF,
+            0xFF,
+            0xFF,
+            0xFF,
+            0xFF,
+            0b1111_0000
+        ]
+    );
+
+    let mut rbb = ReadByteBufferOwned::from_vec(wbb.into_vec());
+
+    assert_eq!(rbb.read_bits(3).expect("Read 3 bits"), 0b101);
+    assert_eq!(rbb.read_bits(64).expect("Read 64 bits"), u64::MAX);
+    assert_eq!(rbb.read_bits(1).expect("Read 1 bit"), 1);
+}
+
+#[test]
+fn test_uint_every_width_and_offset() {
+    for width in 1..=128 {
+        let mask = u128::MAX >> (128 - width);
+        let values = [0, 1, mask, 0xA5A5_5A5A_0F0F_F0F0_C3C3_3C3C_9696_6969 & mask];
+
+        for offset in 0..8 {
+            let mut wbb = WriteByteBufferOwned::new();
+            let mut slice = [0; 80];
+            let mut wbb_slice = WriteByteBufferSlice::new(&mut slice);
+
+            for wbb in [&mut wbb as &mut dyn WriteByteBuffer, &mut wbb_slice] {
+                for value in values {
+                    for _ in 0..offset {
+                        wbb.write_bool(true);
+                    }
+
+                    wbb.write_uint(value, width).unwrap();
+                    wbb.write_u8(0xEE);
+                }
+            }
+
+            let len = wbb_slice.finish().expect("Values fit in the slice");
+            assert_eq!(&slice[..len], wbb.buf(), "width {width}, offset {offset}");
+
+            let mut rbb = ReadByteBufferOwned::from_vec(wbb.into_vec());
+
+            for value in values {
+                for _ in 0..offset {
+                    assert!(rbb.read_bool().expect("Read offset bit"));
+                }
+
+                assert_eq!(
+                    rbb.read_uint(width).expect("Read uint"),
+                    value,
+                    "width {width}, offset {offset}"
+                );
+                assert_eq!(rbb.read_u8().expect("Read marker"), 0xEE);
+            }
+
+            assert!(rbb.is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_int_every_width_and_offset() {
+    for width in 1..=128 {
+        let max = ((1u128 << (width - 1)) - 1) as i128;
+        let min = -max - 1;
+        let values = [0, -1, min, max, min / 3, max / 3];
+
+        for offset in 0..8 {
+            let mut wbb = WriteByteBufferOwned::new();
+
+            for value in values {
+                for _ in 0..offset {
+                    wbb.write_bool(false);
+                }
+
+                wbb.write_int(value, width).unwrap();
+                wbb.write_bool(true);
+            }
+
+            let bytes = wbb.into_vec();
+            let mut rbb = ReadByteBufferStream::new(bytes.as_slice());
+
+            for value in values {
+                for _ in 0..offset {
+                    assert!(!rbb.read_bool().expect("Read offset bit"));
+                }
+
+                assert_eq!(
+                    rbb.read_int(width).expect("Read int"),
+                    value,
+                    "width {width}, offset {offset}"
+                );
+                assert!(rbb.read_bool().expect("Read marker bit"));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_aligned_mixed_bits_and_bytes() {
+    let mut wbb = WriteByteBufferOwned::new();
+
+    wbb.write_bool(true);
+    wbb.write_bytes(&[0xAB, 0xCD]);
+    wbb.write_bool(true);
+    wbb.write_u16(0x1234);
+    wbb.write_bool(true);
+
+    // Each bit starts a new byte after a byte level write, rather than being ORed into the last written byte
+    assert_eq!(
+        wbb.buf(),
+        &[
+            0b1000_0000,
+            0xAB,
+            0xCD,
+            0b1000_0000,
+            0x12,
+            0x34,
+            0b1000_0000
+        ]
+    );
+}
+
+#[test]
+fn test_bitstream_packing() {
+    let mut wbb = WriteByteBufferOwned::new().with_packing(Packing::Bitstream);
+
+    wbb.write_bool(true);
+    wbb.write_bool(false);
+    wbb.write_bool(true);
+    wbb.write_u8(0xFF);
+
+    assert_eq!(wbb.buf(), &[0b1011_1111, 0b1110_0000]);
+
+    wbb.align();
+    wbb.write_u8(0x42);
+
+    assert_eq!(wbb.buf(), &[0b1011_1111, 0b1110_0000, 0x42]);
+}
+
+#[test]
+fn test_bitstream_round_trip() {
+    fn write_all(wbb: &mut dyn WriteByteBuffer) {
+        wbb.write_bool(true);
+        wbb.write_u16(0xBEEF);
+        wbb.write_bits(0b101, 3).unwrap();
+        wbb.write_bytes(&[1, 2, 3]);
+        wbb.write_f64(-0.75);
+        wbb.write_i8(-3);
+        wbb.align();
+        wbb.write_u32(7);
+        wbb.write_bool(false);
+    }
+
+    fn read_all(rbb: &mut impl ReadByteBuffer) {
+        assert!(rbb.read_bool().expect("Read bool"));
+        assert_eq!(rbb.read_u16().expect("Read u16"), 0xBEEF);
+        assert_eq!(rbb.read_bits(3).expect("Read bits"), 0b101);
+        assert_eq!(rbb.read_bytes(3).expect("Read bytes"), &[1, 2, 3]);
+        assert_eq!(rbb.read_f64().expect("Read f64"), -0.75);
+        assert_eq!(rbb.read_i8().expect("Read i8"), -3);
+        rbb.align();
+        assert_eq!(rbb.read_u32().expect("Read u32"), 7);
+        assert!(!rbb.read_bool().expect("Read bool"));
+    }
+
+    let mut owned = WriteByteBufferOwned::new().with_packing(Packing::Bitstream);
+    write_all(&mut owned);
+
+    let mut slice = [0xFF; 32];
+    let mut wbb_slice = WriteByteBufferSlice::new(&mut slice).with_packing(Packing::Bitstream);
+    write_all(&mut wbb_slice);
+
+    let mut stream =
+        WriteByteBufferStream::with_capacity(2, Vec::new()).with_packing(Packing::Bitstream);
+    write_all(&mut stream);
+
+    // 1 + 16 + 3 + 24 + 64 + 8 bits, padded to 15 bytes, followed by a u32 and a bit
+    assert_eq!(owned.len(), 15 + 4 + 1);
+    assert_eq!(wbb_slice.buf(), owned.buf());
+    assert_eq!(
+        stream.finish().expect("Write to vec").as_slice(),
+        owned.buf()
+    );
+
+    let bytes = owned.into_vec();
+
+    read_all(&mut ReadByteBufferSlice::new(&bytes).with_packing(Packing::Bitstream));
+    read_all(&mut ReadByteBufferOwned::from_vec(bytes.clone()).with_packing(Packing::Bitstream));
+    read_all(&mut ReadByteBufferStream::new(bytes.as_slice()).with_packing(Packing::Bitstream));
+
+    let mut owned = ReadByteBufferOwned::from_vec(bytes.clone()).with_packing(Packing::Bitstream);
+    read_all(&mut owned.rbb_ref_mut());
+}
+
+#[test]
+fn test_varint_encoding() {
+    let mut wbb = WriteByteBufferOwned::new();
+
+    wbb.write_varint_u32(0);
+    wbb.write_varint_u32(127);
+    wbb.write_varint_u32(128);
+    wbb.write_varint_u32(300);
+    wbb.write_varint_i32(-1);
+    wbb.write_varint_i32(1);
+    wbb.write_varint_i32(-2);
+    wbb.write_varint_u16(u16::MAX);
+
+    assert_eq!(
+        wbb.buf(),
+        &[0, 0x7F, 0x80, 0x01, 0xAC, 0x02, 1, 2, 3, 0xFF, 0xFF, 0x03]
+    );
+}
+
+#[test]
+fn test_varint_round_trip() {
+    macro_rules! round_trip {
+        ($t:ty, $write:ident, $read:ident) => {
+            let values = [
+                <$t>::MIN,
+                <$t>::MIN / 3,
+                0,
+                1,
+                127,
+                128,
+                <$t>::MAX / 3,
+                <$t>::MAX - 1,
+                <$t>::MAX,
+            ];
+
+            let mut wbb = WriteByteBufferOwned::new();
+
+            for value in values {
+                wbb.$write(value);
+            }
+
+            let mut rbb = ReadByteBufferOwned::from_vec(wbb.into_vec());
+
+            for value in values {
+                assert_eq!(rbb.$read().expect(stringify!($read)), value);
+            }
+
+            assert!(rbb.is_empty());
+        };
+    }
+
+    round_trip!(u16, write_varint_u16, read_varint_u16);
+    round_trip!(u32, write_varint_u32, read_varint_u32);
+    round_trip!(u64, write_varint_u64, read_varint_u64);
+    round_trip!(u128, write_varint_u128, read_varint_u128);
+
+    round_trip!(i16, write_varint_i16, read_varint_i16);
+    round_trip!(i32, write_varint_i32, read_varint_i32);
+    round_trip!(i64, write_varint_i64, read_varint_i64);
+    round_trip!(i128, write_varint_i128, read_varint_i128);
+}
+
+#[test]
+fn test_varint_overflow() {
+    // 2^16 doesn't fit in a u16
+    let mut rbb = ReadByteBufferOwned::from_vec(vec![0x80, 0x80, 0x04]);
+    assert!(rbb.read_varint_u16().is_err());
+
+    // Never ending continuation bits
+    let mut rbb = ReadByteBufferOwned::from_vec(vec![0xFF; 32]);
+    assert!(rbb.read_varint_u64().is_err());
+
+    // Truncated input
+    let mut rbb = ReadByteBufferOwned::from_vec(vec![0x80]);
+    assert!(rbb.read_varint_u32().is_err());
+}
+
+#[test]
+fn test_decode_limits() {
+    let limits = DecodeLimits::UNLIMITED
+        .with_max_alloc(100)
+        .with_max_collection_len(10)
+        .with_max_string_len(20)
+        .with_max_depth(2);
+
+    let bytes = [0u8; 16];
+    let mut rbb = ReadByteBufferSlice::new(&bytes).with_decode_limits(limits);
+
+    rbb.claim_collection(10, 4).expect("Within limits");
+    rbb.claim_string(20).expect("Within limits");
+
+    let err = rbb.claim_collection(11, 1).expect_err("Too many items");
+    assert!(matches!(
+        err.specific_error,
+        SpecificError::LimitExceeded {
+            limit: DecodeLimit::CollectionLen,
+            max: 10,
+            got: 11
+        }
+    ));
+
+    let err = rbb.claim_string(21).expect_err("String too long");
+    assert!(matches!(
+        err.specific_error,
+        SpecificError::LimitExceeded {
+            limit: DecodeLimit::StringLen,
+            ..
+        }
+    ));
+
+    // 60 of 100 bytes are already claimed
+    let err = rbb.claim_collection(10, 5).expect_err("Out of allocation");
+    assert!(matches!(
+        err.specific_error,
+        SpecificError::LimitExceeded {
+            limit: DecodeLimit::Alloc,
+            max: 100,
+            got: 110
+        }
+    ));
+
+    rbb.enter_nested().expect("Depth 1");
+    rbb.enter_nested().expect("Depth 2");
+    rbb.enter_nested().expect_err("Depth 3");
+    rbb.exit_nested();
+    rbb.exit_nested();
+    rbb.enter_nested().expect("Depth 2 again");
+
+    // Sub readers and peeks start from the budget of their parent
+    assert_eq!(rbb.peek().decode_budget(), rbb.decode_budget());
+
+    let sub_reader = rbb.sub_reader(4).expect("Sub reader");
+    assert_eq!(sub_reader.decode_budget().allocated, 60);
+    assert_eq!(sub_reader.decode_budget().limits, limits);
+
+    // Unlimited by default
+    let mut rbb = ReadByteBufferOwned::from_vec(vec![]);
+    rbb.claim_collection(usize::MAX, usize::MAX)
+        .expect("Unlimited");
+}
+
+#[test]
+fn test_ref_mut_shares_decode_budget() {
+    let limits = DecodeLimits::UNLIMITED.with_max_alloc(10);
+
+    let mut owned = ReadByteBufferOwned::from_vec(vec![]).with_decode_limits(limits);
+    let mut slice = ReadByteBufferSlice::new(&[]).with_decode_limits(limits);
+
+    // What's claimed through each ref is charged to the reader it was made from
+    owned.rbb_ref_mut().claim_string(8).expect("Within limits");
+    owned
+        .rbb_ref_mut()
+        .claim_string(8)
+        .expect_err("Out of allocation");
+    assert_eq!(owned.decode_budget().allocated, 8);
+
+    slice.rbb_ref_mut().claim_string(8).expect("Within limits");
+    slice
+        .rbb_ref_mut()
+        .claim_string(8)
+        .expect_err("Out of allocation");
+    assert_eq!(slice.decode_budget().allocated, 8);
+}
+
+#[test]
+fn test_read_error_positions() {
+    let bytes = [0xAB, 0xCD];
+    let mut rbb = ReadByteBufferSlice::new(&bytes).with_packing(Packing::Bitstream);
+
+    rbb.read_bits(3).expect("3 bits");
+    let err = rbb.read_bytes(2).expect_err("Only 1 byte remains");
+    let position = err.position.expect("Position recorded");
+    assert_eq!((position.byte, position.bit), (0, 3));
+    assert!(err.specific_error.is_eof());
+    assert!(err.to_string().ends_with("at byte 0 bit 3"));
+
+    let mut rbb = ReadByteBufferStream::new(&bytes[..]);
+    rbb.read_u16().expect("2 bytes");
+    let err = rbb.read_u8().expect_err("Source is exhausted");
+    assert_eq!(err.position.map(|position| position.byte), Some(2));
+
+    // Running out of bytes maps to the matching io error kind
+    let mut source = &[0x80u8][..];
+    let err = ReadByteBufferStream::new(&mut source)
+        .read_varint_u32()
+        .expect_err("Truncated varint");
+    let io_err = std::io::Error::from(err);
+    assert_eq!(io_err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    let err = ReadError::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+    assert_eq!(err.specific_error, SpecificError::UnexpectedEof);
+
+    let err = ReadError::from(std::io::Error::other("Disconnected"));
+    assert!(matches!(err.specific_error, SpecificError::Custom(_)));
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn test_invalid_bit_counts_error() {
+    let mut wbb = WriteByteBufferOwned::new().with_packing(Packing::Bitstream);
+
+    for count in [0, 65] {
+        let err = wbb.write_bits(0, count).expect_err("Invalid bit count");
+        assert!(matches!(
+            err,
+            WriteError::InvalidBitCount { max: 64, got } if got == count
+        ));
+    }
+
+    wbb.write_uint(0, 129).expect_err("Invalid bit count");
+    wbb.write_int(0, 0).expect_err("Invalid bit count");
+
+    // Nothing was written by the invalid writes
+    wbb.write_bits(0b101, 3).unwrap();
+    assert_eq!(wbb.into_vec(), [0b1010_0000]);
+}
+
+#[test]
+fn test_invalid_reads_error() {
+    let bytes = [0xFF, 0x01];
+    let mut rbb = ReadByteBufferSlice::new(&bytes);
+
+    for count in [0, 65] {
+        let err = rbb.read_bits(count).expect_err("Invalid bit count");
+        assert!(matches!(
+            err.specific_error,
+            SpecificError::InvalidBitCount { max: 64, got } if got == count
+        ));
+    }
+
+    rbb.read_uint(129).expect_err("Invalid bit count");
+    rbb.read_int(0).expect_err("Invalid bit count");
+
+    // A partially read byte is skipped, so only 1 whole byte remains
+    rbb.read_bit().expect("1 bit");
+    let err = rbb.read_bytes(2).expect_err("Only 1 byte remains");
+    assert!(matches!(
+        err.specific_error,
+        SpecificError::Bytes {
+            remaining_bytes: 1,
+            got: 2
+        }
+    ));
+    assert_eq!(rbb.read_bytes(1).expect("1 byte remains"), &[0x01]);
+}
@@ -1,41 +1,236 @@
 mod named;
+mod other;
+mod payload;
+mod tag;
 mod unit;
 mod unnamed;
 
 use crate::derive::enum_derive::named::derive_named;
+use crate::derive::enum_derive::other::derive_other;
+use crate::derive::enum_derive::payload::Payload;
+use crate::derive::enum_derive::tag::Tag;
 use crate::derive::enum_derive::unit::derive_unit;
 use crate::derive::enum_derive::unnamed::derive_unnamed;
-use crate::derive::shared::FunctionBodies;
+use crate::derive::shared::{DeriveMode, FunctionBodies};
 
+use crate::derive::shared::attributes::{ContainerAttributes, EnumTag, VariantAttributes};
 use crate::derive::shared::define_with_vars::define_with_vars;
+use proc_macro2::Span;
 use quote::quote;
-use syn::{DataEnum, Fields, Generics, Variant};
+use syn::{
+    DataEnum, Expr, ExprLit, Fields, GenericArgument, Generics, Lit, PathArguments, Type, Variant,
+};
 
 pub(super) fn impl_derive_enum(
     enum_data: DataEnum,
     enum_name: proc_macro2::Ident,
     generics: Generics,
+    attributes: ContainerAttributes,
+    mode: DeriveMode,
 ) -> proc_macro2::TokenStream {
-    let bodies = get_function_bodies(&enum_name, enum_data);
+    let tag = attributes.tag.unwrap_or_default();
+    let payload = Payload {
+        delimited: attributes.delimited.as_ref(),
+    };
+
+    let checked = check_other_variant(&enum_data, tag, &payload, &mode)
+        .and_then(|other| Ok((other, variant_ids(&enum_data, tag)?)));
+
+    let (other, ids) = match checked {
+        Ok(checked) => checked,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let max_id = ids.iter().map(|(id, _)| *id).max().unwrap_or_default();
+    let tag = Tag::new(tag, max_id);
+
+    let bodies = get_function_bodies(&enum_name, &enum_data, &ids, other, &tag, &payload, &mode);
+
+    define_with_vars(generics, enum_name, bodies, false, None, mode)
+}
+
+/// Finds the `#[serbytes(other)]` variant, checking it can hold the id and payload of unknown variants
+fn check_other_variant<'a>(
+    enum_data: &'a DataEnum,
+    tag: EnumTag,
+    payload: &Payload,
+    mode: &DeriveMode,
+) -> syn::Result<Option<&'a Variant>> {
+    if payload.delimited.is_some() && matches!(mode, DeriveMode::Borrowed(_)) {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`delimited` can't be used together with `borrow`",
+        ));
+    }
+
+    // The width of a bit-packed tag grows as variants are added, so older readers would misread newer tags
+    if payload.delimited.is_some() && matches!(tag, EnumTag::Bits) {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`delimited` can't be used with `tag = bits`, as adding variants can change the width of the tag",
+        ));
+    }
+
+    let mut others = enum_data
+        .variants
+        .iter()
+        .filter(|variant| VariantAttributes::of(variant).other);
+
+    let Some(other) = others.next() else {
+        return Ok(None);
+    };
+
+    if let Some(duplicate) = others.next() {
+        return Err(syn::Error::new_spanned(
+            duplicate,
+            "Only one variant can be `other`",
+        ));
+    }
+
+    if matches!(tag, EnumTag::Bits) {
+        return Err(syn::Error::new_spanned(
+            other,
+            "`other` can't be used with `tag = bits`, as adding variants can change the width of the tag",
+        ));
+    }
+
+    if payload.delimited.is_none() {
+        return Err(syn::Error::new_spanned(
+            other,
+            "`other` needs `#[serbytes(delimited)]` on the enum, so the payload of unknown variants can be skipped",
+        ));
+    }
+
+    let mut fields = other.fields.iter();
+
+    let (Some(id), Some(bytes), None) = (fields.next(), fields.next(), fields.next()) else {
+        return Err(syn::Error::new_spanned(
+            other,
+            "The `other` variant must have two fields, the id and a `Vec<u8>` of the payload",
+        ));
+    };
+
+    if unsigned_max(&id.ty).is_none_or(|max| max > Tag::max_id(tag)) {
+        return Err(syn::Error::new_spanned(
+            &id.ty,
+            format!(
+                "The id of the `other` variant must be an unsigned integer no wider than the {} tag",
+                Tag::name(tag)
+            ),
+        ));
+    }
+
+    if !is_byte_vec(&bytes.ty) {
+        return Err(syn::Error::new_spanned(
+            &bytes.ty,
+            "The payload of the `other` variant must be a `Vec<u8>`",
+        ));
+    }
+
+    Ok(Some(other))
+}
+
+/// The largest value of an unsigned integer type, `None` if the type isn't one
+fn unsigned_max(ty: &Type) -> Option<u64> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    match path.path.get_ident()?.to_string().as_str() {
+        "u8" => Some(u8::MAX.into()),
+        "u16" => Some(u16::MAX.into()),
+        "u32" => Some(u32::MAX.into()),
+        "u64" => Some(u64::MAX),
+        _ => None,
+    }
+}
+
+fn is_byte_vec(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
 
-    define_with_vars(generics, enum_name, bodies, false)
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return false;
+    };
+
+    segment.ident == "Vec"
+        && matches!(
+            arguments.args.first(),
+            Some(GenericArgument::Type(Type::Path(item))) if item.path.is_ident("u8")
+        )
+}
+
+/// The id each variant is written as. That's its `#[serbytes(id = N)]` if it has one, otherwise its
+/// discriminant, like Rust counting up from the previous variant when it isn't given explicitly
+///
+/// The `other` variant has no id of its own, so isn't included
+fn variant_ids(enum_data: &DataEnum, tag: EnumTag) -> syn::Result<Vec<(u64, &Variant)>> {
+    let mut ids: Vec<(u64, &Variant)> = Vec::new();
+    let mut next_id = 0u64;
+
+    for variant in &enum_data.variants {
+        if VariantAttributes::of(variant).other {
+            continue;
+        }
+
+        let id = match (VariantAttributes::of(variant).id, &variant.discriminant) {
+            (Some(id), _) => id.base10_parse()?,
+            (None, Some((_, discriminant))) => discriminant_id(discriminant)?,
+            (None, None) => next_id,
+        };
+
+        if id > Tag::max_id(tag) {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!("The id {id} doesn't fit into the {} tag", Tag::name(tag)),
+            ));
+        }
+
+        if let Some((_, used_by)) = ids.iter().find(|(other_id, _)| *other_id == id) {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!("The id {id} is already used by `{}`", used_by.ident),
+            ));
+        }
+
+        ids.push((id, variant));
+        next_id = id.saturating_add(1);
+    }
+
+    Ok(ids)
+}
+
+fn discriminant_id(discriminant: &Expr) -> syn::Result<u64> {
+    match discriminant {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse(),
+        _ => Err(syn::Error::new_spanned(
+            discriminant,
+            "Only integer literal discriminants can be used as ids, use `#[serbytes(id = N)]` instead",
+        )),
+    }
 }
 
 fn get_function_bodies(
     enum_name: &proc_macro2::Ident,
-    enum_data: DataEnum,
+    enum_data: &DataEnum,
+    ids: &[(u64, &Variant)],
+    other: Option<&Variant>,
+    tag: &Tag,
+    payload: &Payload,
+    mode: &DeriveMode,
 ) -> FunctionBodies<proc_macro2::TokenStream> {
     let mut from_buf_match_tokens = Vec::new();
     let mut to_buf_match_tokens = Vec::new();
     let mut approx_size_match_tokens = Vec::new();
 
-    for (index, variant) in enum_data.variants.iter().enumerate() {
-        assert!(
-            index < 128,
-            "Cannot have more than 127 variants for the enum"
-        );
-
-        let index = index as u8;
+    for &(id, variant) in ids {
         let Variant {
             fields,
             ident: variant_name,
@@ -48,9 +243,13 @@ fn get_function_bodies(
             approx_size_function_body,
             ..
         } = match fields {
-            Fields::Named(named_fields) => derive_named(variant_name, index, named_fields),
-            Fields::Unnamed(unnamed_fields) => derive_unnamed(variant_name, index, unnamed_fields),
-            Fields::Unit => derive_unit(variant_name, index),
+            Fields::Named(named_fields) => {
+                derive_named(variant_name, id, tag, payload, named_fields, mode)
+            }
+            Fields::Unnamed(unnamed_fields) => {
+                derive_unnamed(variant_name, id, tag, payload, unnamed_fields, mode)
+            }
+            Fields::Unit => derive_unit(variant_name, id, tag, payload),
         };
 
         from_buf_match_tokens.push(from_function_body);
@@ -58,48 +257,71 @@ fn get_function_bodies(
         approx_size_match_tokens.push(approx_size_function_body);
     }
 
-    let u8_size_hint = quote! {
-        <u8 as serbytes::prelude::SerBytes>::size_hint()
+    let max_bound = ids.iter().map(|(id, _)| *id).max().unwrap_or_default();
+
+    let unknown_id_match_tokens = match (other, payload.delimited) {
+        (Some(other), Some(len)) => {
+            let FunctionBodies {
+                from_function_body,
+                to_function_body,
+                approx_size_function_body,
+                ..
+            } = derive_other(enum_name, other, tag, len);
+
+            to_buf_match_tokens.push(to_function_body);
+            approx_size_match_tokens.push(approx_size_function_body);
+
+            from_function_body
+        }
+        _ => quote! {
+            _ => {
+                Err(
+                    serbytes::prelude::ReadError::new(
+                        serbytes::prelude::SpecificError::EnumOrdinalOutOfBounds {
+                            max_bound: #max_bound,
+                            got: index
+                        },
+                        stringify!(#enum_name),
+                        None
+                    )
+                    .at(start)
+                )
+            }
+        },
     };
 
+    let tag_size_hint = tag.size_hint();
+    let payload_size_hint = payload.size_hint();
+    let read_tag = tag.read();
+
     let bodies = if !enum_data.variants.is_empty() {
         let approx_size_function_body = quote! {
             let content_size = match self {
                 #(#approx_size_match_tokens)*
             };
 
-            #u8_size_hint + content_size
+            #tag_size_hint + #payload_size_hint + content_size
         };
 
+        let reserve = mode.reserve();
+
         let to_function_body = quote! {
-            serbytes::prelude::WriteByteBufferOwned::reserve(buf, Self::approx_size(self));
+            #reserve
 
             match self {
                 #(#to_buf_match_tokens)*
             }
         };
 
-        let max_bound = enum_data.variants.len() as u8 - 1;
-
         let from_function_body = quote! {
             let mut inner = || {
-                let index = serbytes::prelude::WithParent::with_parent(<u8 as serbytes::prelude::SerBytes>::from_buf(buf), "Enum index")?;
+                let start = serbytes::prelude::ReadByteBuffer::checkpoint(buf);
+                let index = #read_tag?;
 
                 match index {
                     #(#from_buf_match_tokens)*
 
-                    _ => {
-                        Err(
-                            serbytes::prelude::ReadError::new(
-                                bytebuffer::prelude::SpecificError::EnumOrdinalOutOfBounds {
-                                    max_bound: #max_bound,
-                                    got: index
-                                },
-                                stringify!(#enum_name),
-                                None
-                            )
-                        )
-                    }
+                    #unknown_id_match_tokens
                 }
             };
 
@@ -114,23 +336,23 @@ fn get_function_bodies(
         }
     } else {
         let approx_size_function_body = quote! {
-            #u8_size_hint
+            #tag_size_hint
         };
 
-        let to_function_body = quote! {
-            serbytes::prelude::to_buf::<u8>(buf, &0);
-        };
+        let to_function_body = tag.write(&quote! { 0u64 });
 
         let from_function_body = quote! {
             let mut inner = || {
-                let _index = serbytes::prelude::WithParent::with_parent(serbytes::prelude::from_buf::<u8>(buf), "Enum index")?;
+                let start = serbytes::prelude::ReadByteBuffer::checkpoint(buf);
+                let _index = #read_tag?;
 
                 Err(
                     serbytes::prelude::ReadError::new(
-                        bytebuffer::prelude::SpecificError::InvalidEnum,
+                        serbytes::prelude::SpecificError::InvalidEnum,
                         stringify!(#enum_name),
                         None
                     )
+                    .at(start)
                 )
             };
 
@@ -150,7 +372,7 @@ fn get_function_bodies(
         to_function_body: bodies.to_function_body,
         approx_size_function_body: bodies.approx_size_function_body,
         size_hint_function_body: quote! {
-            #u8_size_hint
+            #tag_size_hint + #payload_size_hint
         },
     }
 }
@@ -1,35 +1,51 @@
-use crate::derive::shared::FunctionBodies;
+use crate::derive::enum_derive::payload::Payload;
+use crate::derive::enum_derive::tag::Tag;
 use crate::derive::shared::named_fields::{
     ToBufTokens, impl_approx_size_named_fields, impl_from_named_fields, impl_to_named_fields,
 };
+use crate::derive::shared::{DeriveMode, FunctionBodies};
 use proc_macro2::Ident;
 use quote::quote;
 use syn::FieldsNamed;
 
 pub(super) fn derive_named(
     variant_ident: &Ident,
-    index: u8,
+    id: u64,
+    tag: &Tag,
+    payload: &Payload,
     named_fields: &FieldsNamed,
+    mode: &DeriveMode,
 ) -> FunctionBodies<()> {
-    let from_fields_body = impl_from_named_fields(named_fields);
-    let ToBufTokens { destructure, body } = impl_to_named_fields(named_fields);
-    let approx_size_body = impl_approx_size_named_fields(named_fields);
+    let from_fields_body = impl_from_named_fields(named_fields, mode);
+    let ToBufTokens { destructure, body } = impl_to_named_fields(named_fields, mode);
+    let approx_size_body = impl_approx_size_named_fields(named_fields, mode);
+
+    let read_variant = payload.read(quote! {
+        Ok(Self::#variant_ident {
+            #from_fields_body
+        })
+    });
 
     let from_function_body = quote! {
-        #index => {
-            Ok(Self::#variant_ident {
-                #from_fields_body
-            })
+        #id => {
+            let mut read_variant = || {
+                #read_variant
+            };
+
+            serbytes::prelude::WithPath::in_variant(read_variant(), stringify!(#variant_ident))
         }
     };
 
+    let write_tag = tag.write(&quote! { #id });
+    let write_fields = payload.write(body, approx_size_body.clone());
+
     let to_function_body = quote! {
         Self::#variant_ident {
             #destructure
         } => {
-            serbytes::prelude::to_buf(buf, &#index);
+            #write_tag
 
-            #body
+            #write_fields
         }
     };
 
@@ -0,0 +1,79 @@
+use crate::derive::enum_derive::tag::Tag;
+use crate::derive::shared::FunctionBodies;
+use proc_macro2::Ident;
+use quote::quote;
+use syn::{Fields, Type, Variant};
+
+/// Derives the `#[serbytes(other)]` variant, which is read for every unknown id. Its first field holds
+/// the id and its second the raw bytes of the payload, so they can be written back unchanged
+pub(super) fn derive_other(
+    enum_name: &Ident,
+    variant: &Variant,
+    tag: &Tag,
+    len: &Type,
+) -> FunctionBodies<()> {
+    let variant_ident = &variant.ident;
+    // Checked to be an unsigned integer by `check_other_variant`
+    let id_ty = variant.fields.iter().next().map(|field| &field.ty);
+
+    // Binds the fields to `tag` and `bytes` when matching, and builds the variant from them
+    let pattern = match &variant.fields {
+        Fields::Named(named_fields) => {
+            let mut idents = named_fields.named.iter().map(|field| &field.ident);
+            let (tag_field, bytes_field) = (idents.next(), idents.next());
+
+            quote! {
+                Self::#variant_ident { #tag_field: tag, #bytes_field: bytes }
+            }
+        }
+        _ => quote! {
+            Self::#variant_ident(tag, bytes)
+        },
+    };
+
+    let from_function_body = quote! {
+        _ => {
+            let mut read_variant = || {
+                let tag = <#id_ty as ::core::convert::TryFrom<u64>>::try_from(index).map_err(|_| {
+                    serbytes::prelude::ReadError::new(
+                        serbytes::prelude::SpecificError::EnumOrdinalOutOfBounds {
+                            max_bound: u64::from(<#id_ty>::MAX),
+                            got: index
+                        },
+                        stringify!(#enum_name),
+                        None
+                    )
+                    .at(start)
+                })?;
+                let bytes = <serbytes::prelude::U8Vec<#len> as serbytes::prelude::SerBytes>::from_buf(buf)?.vec;
+
+                Ok(#pattern)
+            };
+
+            serbytes::prelude::WithPath::in_variant(read_variant(), stringify!(#variant_ident))
+        }
+    };
+
+    let write_tag = tag.write_checked(&quote! { u64::from(*tag) });
+
+    let to_function_body = quote! {
+        #pattern => {
+            #write_tag
+            serbytes::prelude::u8_slice_to_buf::<#len>(buf, bytes)?;
+        }
+    };
+
+    let approx_size_function_body = quote! {
+        #pattern => {
+            let _ = tag;
+            bytes.len()
+        }
+    };
+
+    FunctionBodies {
+        from_function_body,
+        to_function_body,
+        approx_size_function_body,
+        size_hint_function_body: (),
+    }
+}
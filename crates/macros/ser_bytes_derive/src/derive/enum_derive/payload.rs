@@ -0,0 +1,52 @@
+use quote::quote;
+use syn::Type;
+
+/// How the fields of a variant are written after its tag
+pub(super) struct Payload<'a> {
+    /// The length type the payload is prefixed with, so it can be skipped without knowing the variant
+    pub(super) delimited: Option<&'a Type>,
+}
+
+impl Payload<'_> {
+    /// Wraps `read`, an expression reading the variant as a `BBReadResult<Self>` from `buf`
+    pub(super) fn read(&self, read: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self.delimited {
+            Some(len) => quote! {
+                serbytes::prelude::block_from_buf_with::<#len, _, _, _>(buf, |buf| {
+                    #read
+                })?
+            },
+            None => read,
+        }
+    }
+
+    /// Wraps `write`, statements writing the variant's fields to `buf`
+    pub(super) fn write(
+        &self,
+        write: proc_macro2::TokenStream,
+        approx_size: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        match self.delimited {
+            Some(len) => quote! {
+                serbytes::prelude::block_to_buf_with::<#len, _>(buf, #approx_size, |buf| {
+                    #write
+
+                    Ok(())
+                })?;
+            },
+            None => write,
+        }
+    }
+
+    /// The size of the length the payload is prefixed with
+    pub(super) fn size_hint(&self) -> proc_macro2::TokenStream {
+        match self.delimited {
+            Some(len) => quote! {
+                <#len as serbytes::prelude::SerBytes>::size_hint()
+            },
+            None => quote! {
+                0
+            },
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::derive::shared::attributes::EnumTag;
+use quote::quote;
+
+/// Generates the reading and writing of an enum's tag, which holds the id of its variant
+pub(super) struct Tag {
+    kind: EnumTag,
+    /// The bits a bit-packed tag takes up
+    bits: u32,
+}
+
+impl Tag {
+    pub(super) fn new(kind: EnumTag, max_id: u64) -> Self {
+        Self {
+            kind,
+            bits: u64::BITS - max_id.leading_zeros(),
+        }
+    }
+
+    /// The largest id a tag of `kind` can hold
+    pub(super) fn max_id(kind: EnumTag) -> u64 {
+        match kind {
+            EnumTag::U8 => u8::MAX.into(),
+            EnumTag::U16 => u16::MAX.into(),
+            EnumTag::U32 => u32::MAX.into(),
+            EnumTag::Varint | EnumTag::Bits => u64::MAX,
+        }
+    }
+
+    pub(super) fn name(kind: EnumTag) -> &'static str {
+        match kind {
+            EnumTag::U8 => "u8",
+            EnumTag::U16 => "u16",
+            EnumTag::U32 => "u32",
+            EnumTag::Varint => "varint",
+            EnumTag::Bits => "bits",
+        }
+    }
+
+    /// Reads the id as a `BBReadResult<u64>`
+    pub(super) fn read(&self) -> proc_macro2::TokenStream {
+        let read = match self.kind {
+            EnumTag::U8 => quote! {
+                <u8 as serbytes::prelude::SerBytes>::from_buf(buf).map(u64::from)
+            },
+            EnumTag::U16 => quote! {
+                <u16 as serbytes::prelude::SerBytes>::from_buf(buf).map(u64::from)
+            },
+            EnumTag::U32 => quote! {
+                <u32 as serbytes::prelude::SerBytes>::from_buf(buf).map(u64::from)
+            },
+            EnumTag::Varint => quote! {
+                <serbytes::prelude::Var<u64> as serbytes::prelude::SerBytes>::from_buf(buf).map(|id| id.0)
+            },
+            // A single variant doesn't need a tag
+            EnumTag::Bits if self.bits == 0 => quote! {
+                serbytes::prelude::BBReadResult::Ok(0u64)
+            },
+            EnumTag::Bits => {
+                let bits = self.bits as usize;
+
+                quote! {
+                    serbytes::prelude::ReadByteBuffer::read_bits(buf, #bits)
+                }
+            }
+        };
+
+        quote! {
+            serbytes::prelude::WithParent::with_parent(#read, "Enum index")
+        }
+    }
+
+    /// Writes `id`, an expression evaluating to a `u64`
+    pub(super) fn write(&self, id: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self.kind {
+            EnumTag::U8 => quote! {
+                serbytes::prelude::to_buf(buf, &(#id as u8));
+            },
+            EnumTag::U16 => quote! {
+                serbytes::prelude::to_buf(buf, &(#id as u16));
+            },
+            EnumTag::U32 => quote! {
+                serbytes::prelude::to_buf(buf, &(#id as u32));
+            },
+            EnumTag::Varint => quote! {
+                serbytes::prelude::to_buf(buf, &serbytes::prelude::Var::<u64>(#id));
+            },
+            EnumTag::Bits if self.bits == 0 => quote! {},
+            EnumTag::Bits => {
+                let bits = self.bits as usize;
+
+                quote! {
+                    serbytes::prelude::WriteByteBuffer::write_bits(buf, #id, #bits)?;
+                }
+            }
+        }
+    }
+
+    /// Writes `id`, an expression evaluating to a `u64` which may not fit into the tag, erroring if it doesn't
+    pub(super) fn write_checked(&self, id: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let write = self.write(&quote! { id });
+        let max_id = Self::max_id(self.kind);
+
+        // Any `u64` fits into a varint
+        if max_id == u64::MAX {
+            return quote! {
+                let id: u64 = #id;
+                #write
+            };
+        }
+
+        let tag_type = Self::name(self.kind);
+
+        quote! {
+            let id: u64 = #id;
+
+            if id > #max_id {
+                return Err(serbytes::prelude::WriteError::IdOverflow {
+                    id,
+                    tag_type: #tag_type
+                });
+            }
+
+            #write
+        }
+    }
+
+    pub(super) fn size_hint(&self) -> proc_macro2::TokenStream {
+        match self.kind {
+            EnumTag::U8 => quote! {
+                <u8 as serbytes::prelude::SerBytes>::size_hint()
+            },
+            EnumTag::U16 => quote! {
+                <u16 as serbytes::prelude::SerBytes>::size_hint()
+            },
+            EnumTag::U32 => quote! {
+                <u32 as serbytes::prelude::SerBytes>::size_hint()
+            },
+            EnumTag::Varint => quote! {
+                <serbytes::prelude::Var<u64> as serbytes::prelude::SerBytes>::size_hint()
+            },
+            EnumTag::Bits => {
+                let bytes = self.bits.div_ceil(8) as usize;
+
+                quote! {
+                    #bytes
+                }
+            }
+        }
+    }
+}
@@ -1,17 +1,33 @@
+use crate::derive::enum_derive::payload::Payload;
+use crate::derive::enum_derive::tag::Tag;
 use crate::derive::shared::FunctionBodies;
 use proc_macro2::Ident;
 use quote::quote;
 
-pub(super) fn derive_unit(variant_name: &Ident, index: u8) -> FunctionBodies<()> {
+pub(super) fn derive_unit(
+    variant_name: &Ident,
+    id: u64,
+    tag: &Tag,
+    payload: &Payload,
+) -> FunctionBodies<()> {
+    let read_variant = payload.read(quote! {
+        Ok(Self::#variant_name)
+    });
+
     let from_function_body = quote! {
-        #index => {
-            Ok(Self::#variant_name)
+        #id => {
+            #read_variant
         }
     };
 
+    let write_tag = tag.write(&quote! { #id });
+    // Delimited enums still write an empty payload, so every variant can be skipped the same way
+    let write_fields = payload.write(quote! {}, quote! { 0 });
+
     let to_function_body = quote! {
         Self::#variant_name => {
-            serbytes::prelude::to_buf(buf, &#index);
+            #write_tag
+            #write_fields
         }
     };
 
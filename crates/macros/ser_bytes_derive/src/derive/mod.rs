@@ -3,25 +3,58 @@ mod shared;
 mod struct_derive;
 
 use crate::derive::enum_derive::impl_derive_enum;
+use crate::derive::shared::DeriveMode;
+use crate::derive::shared::attributes::{ContainerAttributes, validate};
 use crate::derive::struct_derive::impl_derive_struct;
 use quote::quote;
 use syn::{Data, DeriveInput, parse_macro_input, parse_quote};
 
 pub fn ser_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let DeriveInput {
+        attrs,
         data,
         ident,
         mut generics,
         ..
     } = parse_macro_input!(input as DeriveInput);
 
+    if let Err(err) = validate(&attrs, &data) {
+        return err.to_compile_error().into();
+    }
+
+    let attributes = ContainerAttributes::of(&attrs, &data);
+
+    // Only types opting in with `#[serbytes(borrow)]` borrow from the input, a lifetime alone still derives `SerBytes`
+    let mode = if attributes.borrow {
+        let mut lifetimes = generics.lifetimes().map(|param| param.lifetime.clone());
+
+        match (lifetimes.next(), lifetimes.next()) {
+            (Some(lifetime), None) => DeriveMode::Borrowed(lifetime),
+            _ => {
+                return quote! {
+                    compile_error!("`borrow` needs exactly one lifetime parameter to borrow for");
+                }
+                .into();
+            }
+        }
+    } else {
+        DeriveMode::Owned
+    };
+
     for param in generics.type_params_mut() {
-        param.bounds.push(parse_quote!(SerBytes));
+        match &mode {
+            DeriveMode::Owned => param.bounds.push(parse_quote!(SerBytes)),
+            DeriveMode::Borrowed(lifetime) => param
+                .bounds
+                .push(parse_quote!(serbytes::prelude::SerBytesBorrow<#lifetime>)),
+        }
     }
 
     let tokens = match data {
-        Data::Struct(struct_data) => impl_derive_struct(struct_data, ident, generics),
-        Data::Enum(enum_data) => impl_derive_enum(enum_data, ident, generics),
+        Data::Struct(struct_data) => {
+            impl_derive_struct(struct_data, ident, generics, attributes, mode)
+        }
+        Data::Enum(enum_data) => impl_derive_enum(enum_data, ident, generics, attributes, mode),
         Data::Union(_) => {
             quote! {
                 compile_error!("Unions not supported");
@@ -0,0 +1,345 @@
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{
+    Attribute, Data, Field, Fields, Ident, LitInt, LitStr, Path, Token, Type, Variant, parse_quote,
+};
+
+/// The `#[serbytes(...)]` attributes of a field
+#[derive(Default)]
+pub(crate) struct FieldAttributes {
+    /// Neither read nor written, the field is set to `default` when read
+    pub(crate) skip: bool,
+    /// The function called for the value of a skipped field, or of a versioned field the data
+    /// predates, [`Default::default`] if not set
+    pub(crate) default: Option<Path>,
+    /// A module with `value_from_buf` and `try_value_to_buf` functions used to read and write the field,
+    /// with the same signatures as those of `MappedDataProvider`, or a type implementing it
+    pub(crate) with: Option<Path>,
+    /// The `LengthLike` type the length of a `LengthPrefixed` field is written as
+    pub(crate) len: Option<Type>,
+    /// The first version of the type's format the field is written in
+    pub(crate) since: Option<u32>,
+    /// The version the field was removed in, it's only read from data written by earlier versions
+    pub(crate) until: Option<u32>,
+}
+
+impl FieldAttributes {
+    pub(crate) fn parse(field: &Field) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+
+        for attr in serbytes_attributes(&field.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    if attributes.skip {
+                        return Err(meta.error("Duplicate serbytes attribute"));
+                    }
+
+                    attributes.skip = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    let path = meta.value()?.parse::<LitStr>()?.parse()?;
+                    set_once(&meta, &mut attributes.default, path)
+                } else if meta.path.is_ident("with") {
+                    let path = meta.value()?.parse::<LitStr>()?.parse()?;
+                    set_once(&meta, &mut attributes.with, path)
+                } else if meta.path.is_ident("len") {
+                    let ty = meta.value()?.parse()?;
+                    set_once(&meta, &mut attributes.len, ty)
+                } else if meta.path.is_ident("since") {
+                    let since = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                    set_once(&meta, &mut attributes.since, since)
+                } else if meta.path.is_ident("until") {
+                    let until = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                    set_once(&meta, &mut attributes.until, until)
+                } else {
+                    Err(meta.error("Unknown serbytes field attribute"))
+                }
+            })?;
+        }
+
+        attributes.check(field)?;
+
+        Ok(attributes)
+    }
+
+    /// Parses the attributes of a field which has already been through [`validate`]
+    pub(crate) fn of(field: &Field) -> Self {
+        Self::parse(field).unwrap_or_default()
+    }
+
+    fn check(&self, field: &Field) -> syn::Result<()> {
+        let error = |message| Err(syn::Error::new_spanned(field, message));
+
+        if self.default.is_some() && !self.skip && !self.is_versioned() {
+            return error("`default` can only be used on skipped or versioned fields");
+        }
+
+        if self.skip && (self.with.is_some() || self.len.is_some()) {
+            return error("Skipped fields can't have `with` or `len`");
+        }
+
+        if self.skip && self.is_versioned() {
+            return error("Skipped fields can't have `since` or `until`");
+        }
+
+        if let (Some(since), Some(until)) = (self.since, self.until)
+            && since >= until
+        {
+            return error("`until` must be a later version than `since`");
+        }
+
+        if self.with.is_some() && self.len.is_some() {
+            return error("`with` and `len` can't be used together");
+        }
+
+        Ok(())
+    }
+
+    /// Whether the field is only in some versions of the type's format
+    pub(crate) fn is_versioned(&self) -> bool {
+        self.since.is_some() || self.until.is_some()
+    }
+
+    /// The condition for the field being in `version`, `None` if it's in every version
+    pub(crate) fn version_condition(
+        &self,
+        version: &proc_macro2::TokenStream,
+    ) -> Option<proc_macro2::TokenStream> {
+        // Every version is at least 0, so `since = 0` doesn't need checking
+        let since = self
+            .since
+            .filter(|since| *since > 0)
+            .map(|since| quote! { #version >= #since });
+        let until = self.until.map(|until| quote! { #version < #until });
+
+        match (since, until) {
+            (Some(since), Some(until)) => Some(quote! { #since && #until }),
+            (since, until) => since.or(until),
+        }
+    }
+}
+
+/// How an enum writes the ordinal of its variants
+#[derive(Copy, Clone, Default)]
+pub(crate) enum EnumTag {
+    #[default]
+    U8,
+    U16,
+    U32,
+    /// A `Var<u64>`
+    Varint,
+    /// As few bits as can hold the largest ordinal
+    Bits,
+}
+
+/// The `#[serbytes(...)]` attributes of the type being derived
+#[derive(Default)]
+pub(crate) struct ContainerAttributes {
+    pub(crate) tag: Option<EnumTag>,
+    /// The `LengthLike` type the length of every variant's payload is written as, so that readers can
+    /// skip the payloads of variants they don't know
+    pub(crate) delimited: Option<Type>,
+    /// The current version of a struct's format, which the `since` and `until` of its fields refer to
+    pub(crate) version: Option<u32>,
+    /// Derives `SerBytesBorrow` for the type's lifetime instead of `SerBytes`, so fields can borrow from the input
+    pub(crate) borrow: bool,
+}
+
+impl ContainerAttributes {
+    pub(crate) fn parse(attrs: &[Attribute], data: &Data) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+
+        for attr in serbytes_attributes(attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    if !matches!(data, Data::Enum(_)) {
+                        return Err(meta.error("`tag` can only be used on enums"));
+                    }
+
+                    let tag = match meta.value()?.parse::<Ident>()?.to_string().as_str() {
+                        "u8" => EnumTag::U8,
+                        "u16" => EnumTag::U16,
+                        "u32" => EnumTag::U32,
+                        "varint" => EnumTag::Varint,
+                        "bits" => EnumTag::Bits,
+                        _ => {
+                            return Err(meta
+                                .error("Expected one of `u8`, `u16`, `u32`, `varint` or `bits`"));
+                        }
+                    };
+
+                    set_once(&meta, &mut attributes.tag, tag)
+                } else if meta.path.is_ident("delimited") {
+                    if !matches!(data, Data::Enum(_)) {
+                        return Err(meta.error("`delimited` can only be used on enums"));
+                    }
+
+                    // The length is a `u16` like a `SizedBlock`'s unless given
+                    let len = if meta.input.peek(Token![=]) {
+                        meta.value()?.parse()?
+                    } else {
+                        parse_quote!(u16)
+                    };
+
+                    set_once(&meta, &mut attributes.delimited, len)
+                } else if meta.path.is_ident("borrow") {
+                    if attributes.borrow {
+                        return Err(meta.error("Duplicate serbytes attribute"));
+                    }
+
+                    attributes.borrow = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("version") {
+                    if !matches!(data, Data::Struct(_)) {
+                        return Err(meta.error("`version` can only be used on structs"));
+                    }
+
+                    let version = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                    set_once(&meta, &mut attributes.version, version)
+                } else {
+                    Err(meta.error("Unknown serbytes attribute"))
+                }
+            })?;
+        }
+
+        Ok(attributes)
+    }
+
+    /// Parses the attributes of a type which has already been through [`validate`]
+    pub(crate) fn of(attrs: &[Attribute], data: &Data) -> Self {
+        Self::parse(attrs, data).unwrap_or_default()
+    }
+}
+
+/// The `#[serbytes(...)]` attributes of an enum variant
+#[derive(Default)]
+pub(crate) struct VariantAttributes {
+    /// The id the variant is written as, overriding its discriminant
+    pub(crate) id: Option<LitInt>,
+    /// The variant read for every unknown id, holding the id and the raw bytes of the payload
+    pub(crate) other: bool,
+}
+
+impl VariantAttributes {
+    pub(crate) fn parse(variant: &Variant) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+
+        for attr in serbytes_attributes(&variant.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("id") {
+                    let id = meta.value()?.parse()?;
+                    set_once(&meta, &mut attributes.id, id)
+                } else if meta.path.is_ident("other") {
+                    if attributes.other {
+                        return Err(meta.error("Duplicate serbytes attribute"));
+                    }
+
+                    attributes.other = true;
+
+                    Ok(())
+                } else {
+                    Err(meta.error("Unknown serbytes variant attribute"))
+                }
+            })?;
+        }
+
+        if attributes.other && attributes.id.is_some() {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "The `other` variant is read for every unknown id, so can't have an `id` itself",
+            ));
+        }
+
+        Ok(attributes)
+    }
+
+    /// Parses the attributes of a variant which has already been through [`validate`]
+    pub(crate) fn of(variant: &Variant) -> Self {
+        Self::parse(variant).unwrap_or_default()
+    }
+}
+
+/// Checks every `#[serbytes(...)]` attribute of the type, combining all the errors into one
+pub(crate) fn validate(attrs: &[Attribute], data: &Data) -> syn::Result<()> {
+    let container = ContainerAttributes::parse(attrs, data);
+    let version = container
+        .as_ref()
+        .ok()
+        .and_then(|container| container.version);
+    let mut results = vec![container.map(|_| ())];
+
+    let fields: Vec<&Fields> = match data {
+        Data::Struct(struct_data) => vec![&struct_data.fields],
+        Data::Enum(enum_data) => {
+            for variant in &enum_data.variants {
+                results.push(VariantAttributes::parse(variant).map(|_| ()));
+            }
+
+            enum_data
+                .variants
+                .iter()
+                .map(|variant| &variant.fields)
+                .collect()
+        }
+        Data::Union(_) => Vec::new(),
+    };
+
+    for field in fields.into_iter().flatten() {
+        results.push(
+            FieldAttributes::parse(field)
+                .and_then(|attributes| check_version(field, &attributes, version)),
+        );
+    }
+
+    combine_errors(results)
+}
+
+/// Checks the `since` and `until` of a field refer to the `version` of its struct
+fn check_version(
+    field: &Field,
+    attributes: &FieldAttributes,
+    version: Option<u32>,
+) -> syn::Result<()> {
+    let error = |message| Err(syn::Error::new_spanned(field, message));
+
+    match (version, attributes.since) {
+        _ if !attributes.is_versioned() => Ok(()),
+        (None, _) => {
+            error("`since` and `until` can only be used in structs with `#[serbytes(version = N)]`")
+        }
+        (Some(version), Some(since)) if since > version => error(
+            "`since` can't be later than the current version, the field would never be written",
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Combines the errors of every result into one, so they're all reported at once
+fn combine_errors(results: impl IntoIterator<Item = syn::Result<()>>) -> syn::Result<()> {
+    let mut errors: Option<syn::Error> = None;
+
+    for err in results.into_iter().filter_map(Result::err) {
+        match &mut errors {
+            Some(errors) => errors.combine(err),
+            None => errors = Some(err),
+        }
+    }
+
+    errors.map_or(Ok(()), Err)
+}
+
+fn serbytes_attributes(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("serbytes"))
+}
+
+fn set_once<T>(meta: &ParseNestedMeta, slot: &mut Option<T>, value: T) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(meta.error("Duplicate serbytes attribute"));
+    }
+
+    *slot = Some(value);
+
+    Ok(())
+}
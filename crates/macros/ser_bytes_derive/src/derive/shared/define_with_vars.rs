@@ -1,4 +1,4 @@
-use crate::derive::shared::FunctionBodies;
+use crate::derive::shared::{DeriveMode, FunctionBodies};
 use quote::quote;
 use syn::Generics;
 
@@ -7,6 +7,8 @@ pub(crate) fn define_with_vars(
     ty_name: proc_macro2::Ident,
     function_bodies: FunctionBodies<proc_macro2::TokenStream>,
     is_buf_unused: bool,
+    version: Option<u32>,
+    mode: DeriveMode,
 ) -> proc_macro2::TokenStream {
     let FunctionBodies {
         from_function_body,
@@ -27,17 +29,108 @@ pub(crate) fn define_with_vars(
         }
     };
 
+    if let DeriveMode::Borrowed(lifetime) = mode {
+        return quote! {
+            impl #impl_generics serbytes::prelude::SerBytesBorrow<#lifetime> for #ty_name #ty_generics #where_clause {
+                fn from_buf_borrowed(#buf_name: &mut serbytes::prelude::ReadByteBufferSlice<#lifetime>) -> serbytes::prelude::BBReadResult<Self> {
+                    let result = serbytes::prelude::from_buf_nested(#buf_name, |#buf_name| {
+                        #from_function_body
+                    });
+
+                    serbytes::prelude::WithPath::in_type(result, stringify!(#ty_name))
+                }
+
+                fn to_buf_borrowed(&self, #buf_name: &mut dyn serbytes::prelude::WriteByteBuffer) {
+                    serbytes::prelude::expect_written(
+                        <Self as serbytes::prelude::SerBytesBorrow<#lifetime>>::try_to_buf_borrowed(self, #buf_name)
+                    );
+                }
+
+                fn try_to_buf_borrowed(&self, #buf_name: &mut dyn serbytes::prelude::WriteByteBuffer) -> serbytes::prelude::BBWriteResult<()> {
+                    #to_function_body
+
+                    Ok(())
+                }
+
+                fn approx_size_borrowed(&self) -> usize {
+                    #approx_size_function_body
+                }
+            }
+        };
+    }
+
+    let read_nested = quote! {
+        serbytes::prelude::from_buf_nested(#buf_name, |#buf_name| {
+            #from_function_body
+        })
+    };
+
+    // Versioned types read the fields of the given version, `SerBytes` reads the current one
+    let (from_buf_body, versioned_impl) = match version {
+        Some(version) => (
+            quote! {
+                <Self as serbytes::prelude::SerBytesVersioned>::from_buf_versioned(#buf_name, #version)
+            },
+            quote! {
+                impl #impl_generics serbytes::prelude::SerBytesVersioned for #ty_name #ty_generics #where_clause {
+                    const CURRENT_VERSION: u32 = #version;
+
+                    fn from_buf_versioned<__R>(#buf_name: &mut __R, version: u32) -> serbytes::prelude::BBReadResult<Self>
+                    where
+                        __R: serbytes::prelude::ReadByteBuffer + ?Sized,
+                        Self: Sized,
+                    {
+                        let result = if version > #version {
+                            Err(
+                                serbytes::prelude::ReadError::new(
+                                    serbytes::prelude::SpecificError::UnknownVersion {
+                                        current: #version,
+                                        got: version
+                                    },
+                                    stringify!(#ty_name),
+                                    None
+                                )
+                                .at(serbytes::prelude::ReadByteBuffer::checkpoint(#buf_name))
+                            )
+                        } else {
+                            #read_nested
+                        };
+
+                        serbytes::prelude::WithPath::in_type(result, stringify!(#ty_name))
+                    }
+                }
+            },
+        ),
+        None => (
+            quote! {
+                let result = #read_nested;
+
+                serbytes::prelude::WithPath::in_type(result, stringify!(#ty_name))
+            },
+            quote! {},
+        ),
+    };
+
     quote! {
         impl #impl_generics serbytes::prelude::SerBytes for #ty_name #ty_generics #where_clause {
-            fn from_buf(#buf_name: &mut serbytes::prelude::ReadByteBufferRefMut) -> serbytes::prelude::BBReadResult<Self>
+            fn from_buf<__R>(#buf_name: &mut __R) -> serbytes::prelude::BBReadResult<Self>
             where
+                __R: serbytes::prelude::ReadByteBuffer + ?Sized,
                 Self: Sized,
             {
-                #from_function_body
+                #from_buf_body
             }
 
-            fn to_buf(&self, #buf_name: &mut serbytes::prelude::WriteByteBufferOwned) {
+            fn to_buf(&self, #buf_name: &mut dyn serbytes::prelude::WriteByteBuffer) {
+                serbytes::prelude::expect_written(
+                    <Self as serbytes::prelude::SerBytes>::try_to_buf(self, #buf_name)
+                );
+            }
+
+            fn try_to_buf(&self, #buf_name: &mut dyn serbytes::prelude::WriteByteBuffer) -> serbytes::prelude::BBWriteResult<()> {
                 #to_function_body
+
+                Ok(())
             }
 
             fn size_hint() -> usize
@@ -51,5 +144,7 @@ pub(crate) fn define_with_vars(
                 #approx_size_function_body
             }
         }
+
+        #versioned_impl
     }
 }
@@ -1,7 +1,9 @@
+use crate::derive::shared::attributes::FieldAttributes;
 use quote::quote;
 use syn::punctuated::Punctuated;
-use syn::{Field, Token};
+use syn::{Field, GenericArgument, Lifetime, PathArguments, Token, Type};
 
+pub(crate) mod attributes;
 pub(super) mod define_with_vars;
 pub(crate) mod named_fields;
 pub(crate) mod unnamed_fields;
@@ -13,6 +15,162 @@ pub(super) struct FunctionBodies<H> {
     pub(super) size_hint_function_body: H,
 }
 
+/// The trait being derived. Types with `#[serbytes(borrow)]` derive `SerBytesBorrow` for their lifetime
+pub(crate) enum DeriveMode {
+    Owned,
+    Borrowed(Lifetime),
+}
+
+impl DeriveMode {
+    /// Reads a field, recording `name` in the path of any error. Versioned fields are only read when
+    /// they're in the `version` being read, which is in scope in the versioned reader
+    pub(crate) fn read_field(&self, name: &str, field: &Field) -> proc_macro2::TokenStream {
+        let attributes = FieldAttributes::of(field);
+        let ty = &field.ty;
+
+        let default = match &attributes.default {
+            Some(default) => quote! { #default() },
+            None => quote! { ::core::default::Default::default() },
+        };
+
+        if attributes.skip {
+            return default;
+        }
+
+        let read = match (&attributes.with, &attributes.len, self) {
+            (Some(with), _, _) => quote! {
+                {
+                    use serbytes::prelude::MappedDataProvider as _;
+
+                    #with::value_from_buf(buf)
+                }
+            },
+            (_, Some(len), _) => quote! {
+                serbytes::prelude::prefixed_from_buf::<#ty, #len>(buf)
+            },
+            (_, _, Self::Owned) => quote! {
+                serbytes::prelude::from_buf(buf)
+            },
+            (_, _, Self::Borrowed(_)) if is_borrowable_cow(ty) => quote! {
+                serbytes::prelude::cow_from_buf_borrowed(buf)
+            },
+            (_, _, Self::Borrowed(_)) => quote! {
+                serbytes::prelude::from_buf_borrowed(buf)
+            },
+        };
+
+        let read = quote! {
+            serbytes::prelude::WithPath::in_field(#read, #name)?
+        };
+
+        match attributes.version_condition(&quote! { version }) {
+            Some(condition) => quote! {
+                if #condition { #read } else { #default }
+            },
+            None => read,
+        }
+    }
+
+    pub(crate) fn write_field(
+        &self,
+        field: &Field,
+        value: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let attributes = FieldAttributes::of(field);
+        let ty = &field.ty;
+
+        if attributes.skip {
+            return quote! {
+                let _ = #value;
+            };
+        }
+
+        let write = match (&attributes.with, &attributes.len, self) {
+            (Some(with), _, _) => quote! {
+                {
+                    use serbytes::prelude::MappedDataProvider as _;
+
+                    #with::try_value_to_buf(#value, buf)?;
+                }
+            },
+            (_, Some(len), _) => quote! {
+                serbytes::prelude::prefixed_to_buf::<#ty, #len>(buf, #value)?;
+            },
+            (_, _, Self::Owned) => quote! {
+                serbytes::prelude::try_to_buf(buf, #value)?;
+            },
+            (_, _, Self::Borrowed(_)) => quote! {
+                serbytes::prelude::try_to_buf_borrowed(buf, #value)?;
+            },
+        };
+
+        match attributes.version_condition(&current_version()) {
+            Some(condition) => quote! {
+                if #condition {
+                    #write
+                } else {
+                    let _ = #value;
+                }
+            },
+            None => write,
+        }
+    }
+
+    pub(crate) fn approx_size_field(
+        &self,
+        field: &Field,
+        value: &proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let attributes = FieldAttributes::of(field);
+        let ty = &field.ty;
+
+        // The size of fields written by a `with` module isn't known
+        if attributes.skip || attributes.with.is_some() {
+            return quote! {
+                {
+                    let _ = #value;
+                    0
+                }
+            };
+        }
+
+        let approx_size = match (&attributes.len, self) {
+            (Some(len), _) => quote! {
+                serbytes::prelude::prefixed_approx_size::<#ty, #len>(#value)
+            },
+            (None, Self::Owned) => quote! {
+                <#ty as serbytes::prelude::SerBytes>::approx_size(#value)
+            },
+            (None, Self::Borrowed(lifetime)) => quote! {
+                <#ty as serbytes::prelude::SerBytesBorrow<#lifetime>>::approx_size_borrowed(#value)
+            },
+        };
+
+        match attributes.version_condition(&current_version()) {
+            Some(condition) => quote! {
+                (if #condition { #approx_size } else { 0 })
+            },
+            None => approx_size,
+        }
+    }
+
+    /// Reserves space in the buffer for the type being written
+    pub(crate) fn reserve(&self) -> proc_macro2::TokenStream {
+        let approx_size = match self {
+            Self::Owned => quote! {
+                Self::approx_size(self)
+            },
+            Self::Borrowed(lifetime) => quote! {
+                <Self as serbytes::prelude::SerBytesBorrow<#lifetime>>::approx_size_borrowed(self)
+            },
+        };
+
+        quote! {
+            serbytes::prelude::WriteByteBuffer::reserve(buf, #approx_size);
+        }
+    }
+}
+
 pub(super) fn impl_size_hint(
     named_fields: &Punctuated<Field, Token![,]>,
 ) -> proc_macro2::TokenStream {
@@ -20,10 +178,29 @@ pub(super) fn impl_size_hint(
         let mut size_hint_body_tokens = Vec::new();
 
         for field in named_fields {
-            let Field { ty, .. } = field;
+            let attributes = FieldAttributes::of(field);
+            let ty = &field.ty;
 
-            size_hint_body_tokens.push(quote! {
-                   <#ty as serbytes::prelude::SerBytes>::size_hint()
+            let size_hint = match (
+                &attributes.len,
+                attributes.skip || attributes.with.is_some(),
+            ) {
+                (_, true) => quote! {
+                    0
+                },
+                (Some(len), false) => quote! {
+                    <#len as serbytes::prelude::SerBytes>::size_hint()
+                },
+                (None, false) => quote! {
+                    <#ty as serbytes::prelude::SerBytes>::size_hint()
+                },
+            };
+
+            size_hint_body_tokens.push(match attributes.version_condition(&current_version()) {
+                Some(condition) => quote! {
+                    (if #condition { #size_hint } else { 0 })
+                },
+                None => size_hint,
             });
         }
 
@@ -36,3 +213,36 @@ pub(super) fn impl_size_hint(
         }
     }
 }
+
+/// Whether the type is a `Cow<str>` or `Cow<[u8]>`, which borrowed types read without copying
+fn is_borrowable_cow(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return false;
+    };
+
+    let borrowed = arguments.args.iter().find_map(|argument| match argument {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+
+    segment.ident == "Cow"
+        && borrowed.is_some_and(|borrowed| {
+            matches!(borrowed, Type::Path(path) if path.path.is_ident("str"))
+                || matches!(borrowed, Type::Slice(slice) if matches!(&*slice.elem, Type::Path(path) if path.path.is_ident("u8")))
+        })
+}
+
+/// The version fields are written in, only used by structs with `#[serbytes(version = N)]`
+fn current_version() -> proc_macro2::TokenStream {
+    quote! {
+        <Self as serbytes::prelude::SerBytesVersioned>::CURRENT_VERSION
+    }
+}
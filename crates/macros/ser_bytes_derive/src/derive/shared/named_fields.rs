@@ -1,13 +1,21 @@
+use crate::derive::shared::DeriveMode;
 use quote::quote;
+use syn::ext::IdentExt;
 use syn::{Field, FieldsNamed};
 
-pub(crate) fn impl_from_named_fields(named_fields: &FieldsNamed) -> proc_macro2::TokenStream {
+pub(crate) fn impl_from_named_fields(
+    named_fields: &FieldsNamed,
+    mode: &DeriveMode,
+) -> proc_macro2::TokenStream {
     let mut from_body = Vec::new();
 
     for field in &named_fields.named {
         let Field { ident, .. } = field;
+        let name = ident.as_ref().map(|ident| ident.unraw().to_string());
+        let read_field = mode.read_field(name.as_deref().unwrap_or_default(), field);
+
         from_body.push(quote! {
-            #ident: serbytes::prelude::from_buf(buf)?
+            #ident: #read_field
         });
     }
 
@@ -21,18 +29,18 @@ pub(crate) struct ToBufTokens {
     pub(crate) body: proc_macro2::TokenStream,
 }
 
-pub(crate) fn impl_to_named_fields(named_fields: &FieldsNamed) -> ToBufTokens {
+pub(crate) fn impl_to_named_fields(named_fields: &FieldsNamed, mode: &DeriveMode) -> ToBufTokens {
     let mut to_destructure_body = Vec::new();
     let mut to_body = Vec::new();
 
-    for Field { ident, .. } in &named_fields.named {
+    for field in &named_fields.named {
+        let ident = &field.ident;
+
         to_destructure_body.push(quote! {
             #ident
         });
 
-        to_body.push(quote! {
-            serbytes::prelude::to_buf(buf, #ident);
-        });
+        to_body.push(mode.write_field(field, &quote! { #ident }));
     }
 
     let destructure = quote! {
@@ -48,15 +56,14 @@ pub(crate) fn impl_to_named_fields(named_fields: &FieldsNamed) -> ToBufTokens {
 
 pub(crate) fn impl_approx_size_named_fields(
     named_fields: &FieldsNamed,
+    mode: &DeriveMode,
 ) -> proc_macro2::TokenStream {
     let mut approx_size_body_tokens = Vec::new();
 
     for field in &named_fields.named {
-        let Field { ident, ty, .. } = field;
+        let ident = &field.ident;
 
-        approx_size_body_tokens.push(quote! {
-               <#ty as serbytes::prelude::SerBytes>::approx_size(&#ident)
-        });
+        approx_size_body_tokens.push(mode.approx_size_field(field, &quote! { &#ident }));
     }
 
     let approx_size_body = quote! {
@@ -1,15 +1,17 @@
+use crate::derive::shared::DeriveMode;
 use crate::derive::shared::named_fields::ToBufTokens;
 use proc_macro2::{Ident, Span};
 use quote::quote;
 use syn::FieldsUnnamed;
 
-pub(crate) fn impl_from_unnamed_fields(unnamed_fields: &FieldsUnnamed) -> proc_macro2::TokenStream {
+pub(crate) fn impl_from_unnamed_fields(
+    unnamed_fields: &FieldsUnnamed,
+    mode: &DeriveMode,
+) -> proc_macro2::TokenStream {
     let mut from_body = Vec::new();
 
-    for _ in &unnamed_fields.unnamed {
-        from_body.push(quote! {
-            serbytes::prelude::from_buf(buf)?
-        });
+    for (i, field) in unnamed_fields.unnamed.iter().enumerate() {
+        from_body.push(mode.read_field(&i.to_string(), field));
     }
 
     let from = quote! {
@@ -19,24 +21,21 @@ pub(crate) fn impl_from_unnamed_fields(unnamed_fields: &FieldsUnnamed) -> proc_m
     from
 }
 
-pub(crate) fn impl_to_unnamed_fields(unnamed_fields: &FieldsUnnamed) -> ToBufTokens {
+pub(crate) fn impl_to_unnamed_fields(
+    unnamed_fields: &FieldsUnnamed,
+    mode: &DeriveMode,
+) -> ToBufTokens {
     let mut to_destructure_body = Vec::new();
     let mut to_body = Vec::new();
 
-    let mut i = 0;
-
-    for _ in &unnamed_fields.unnamed {
+    for (i, field) in unnamed_fields.unnamed.iter().enumerate() {
         let destructure_var = Ident::new(&format!("field{}", i), Span::call_site());
 
         to_destructure_body.push(quote! {
             #destructure_var
         });
 
-        to_body.push(quote! {
-            serbytes::prelude::to_buf(buf, #destructure_var);
-        });
-
-        i += 1;
+        to_body.push(mode.write_field(field, &quote! { #destructure_var }));
     }
 
     let destructure = quote! {
@@ -52,20 +51,14 @@ pub(crate) fn impl_to_unnamed_fields(unnamed_fields: &FieldsUnnamed) -> ToBufTok
 
 pub(crate) fn impl_approx_size_unnamed_fields(
     unnamed_fields: &FieldsUnnamed,
+    mode: &DeriveMode,
 ) -> proc_macro2::TokenStream {
     let mut approx_size_body_tokens = Vec::new();
 
-    let mut i = 0;
-
-    for unnamed_field in &unnamed_fields.unnamed {
+    for (i, unnamed_field) in unnamed_fields.unnamed.iter().enumerate() {
         let destructure_var = Ident::new(&format!("field{}", i), Span::call_site());
-        let field_ty = &unnamed_field.ty;
-
-        approx_size_body_tokens.push(quote! {
-            <#field_ty as serbytes::prelude::SerBytes>::approx_size(#destructure_var)
-        });
-
-        i += 1;
+        approx_size_body_tokens
+            .push(mode.approx_size_field(unnamed_field, &quote! { #destructure_var }));
     }
 
     let approx_size_body = quote! {
@@ -1,7 +1,7 @@
 use crate::derive::shared::named_fields::{
     ToBufTokens, impl_approx_size_named_fields, impl_from_named_fields, impl_to_named_fields,
 };
-use crate::derive::shared::{FunctionBodies, impl_size_hint};
+use crate::derive::shared::{DeriveMode, FunctionBodies, impl_size_hint};
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use syn::FieldsNamed;
@@ -9,10 +9,11 @@ use syn::FieldsNamed;
 pub(super) fn derive_named(
     struct_name: &Ident,
     named_fields: &FieldsNamed,
+    mode: &DeriveMode,
 ) -> FunctionBodies<TokenStream> {
-    let from_body = impl_from_named_fields(named_fields);
-    let ToBufTokens { destructure, body } = impl_to_named_fields(named_fields);
-    let approx_size_body = impl_approx_size_named_fields(named_fields);
+    let from_body = impl_from_named_fields(named_fields, mode);
+    let ToBufTokens { destructure, body } = impl_to_named_fields(named_fields, mode);
+    let approx_size_body = impl_approx_size_named_fields(named_fields, mode);
     let size_hint_function_body = impl_size_hint(&named_fields.named);
 
     let from_function_body = quote! {
@@ -23,9 +24,11 @@ pub(super) fn derive_named(
 
     let not_empty = !named_fields.named.is_empty();
 
+    let reserve = mode.reserve();
+
     let to_function_body = if not_empty {
         quote! {
-            serbytes::prelude::WriteByteBufferOwned::reserve(buf, Self::approx_size(self));
+            #reserve
 
             let #struct_name {
                 #destructure
@@ -2,7 +2,7 @@ use crate::derive::shared::named_fields::ToBufTokens;
 use crate::derive::shared::unnamed_fields::{
     impl_approx_size_unnamed_fields, impl_from_unnamed_fields, impl_to_unnamed_fields,
 };
-use crate::derive::shared::{FunctionBodies, impl_size_hint};
+use crate::derive::shared::{DeriveMode, FunctionBodies, impl_size_hint};
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use syn::FieldsUnnamed;
@@ -10,17 +10,20 @@ use syn::FieldsUnnamed;
 pub(super) fn derive_unnamed(
     struct_name: &Ident,
     unnamed_fields: &FieldsUnnamed,
+    mode: &DeriveMode,
 ) -> FunctionBodies<TokenStream> {
-    let from_body = impl_from_unnamed_fields(unnamed_fields);
-    let ToBufTokens { destructure, body } = impl_to_unnamed_fields(unnamed_fields);
-    let approx_size_body = impl_approx_size_unnamed_fields(unnamed_fields);
+    let from_body = impl_from_unnamed_fields(unnamed_fields, mode);
+    let ToBufTokens { destructure, body } = impl_to_unnamed_fields(unnamed_fields, mode);
+    let approx_size_body = impl_approx_size_unnamed_fields(unnamed_fields, mode);
     let size_hint_function_body = impl_size_hint(&unnamed_fields.unnamed);
 
     let not_empty = !unnamed_fields.unnamed.is_empty();
 
+    let reserve = mode.reserve();
+
     let to_function_body = if not_empty {
         quote! {
-            serbytes::prelude::WriteByteBufferOwned::reserve(buf, Self::approx_size(self));
+            #reserve
 
             let #struct_name(#destructure) = self;
 
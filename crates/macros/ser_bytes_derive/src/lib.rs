@@ -7,8 +7,46 @@ use crate::derive::ser_derive;
 ///
 /// For enum derives, it uses an 8 bit integer to serialize the ordinal
 /// As such, the maximum amount of variants that an enum can contain is 2^8 (256)
-
-#[proc_macro_derive(SerBytes)]
+///
+/// `#[serbytes(tag = u16)]`, `#[serbytes(tag = u32)]` or `#[serbytes(tag = varint)]` on the enum write the
+/// ordinal as a wider integer instead. `#[serbytes(tag = bits)]` writes it in as few bits as can hold the
+/// largest ordinal, i.e. 3 bits for 5 variants, which pack tightly with `Packing::Bitstream`
+///
+/// `#[serbytes(delimited)]` on the enum prefixes the payload of every variant with its length as a `u16`, like
+/// a `SizedBlock`, or as the given type with `#[serbytes(delimited = u32)]`. An enum that's delimited can
+/// mark a variant with two fields, i.e. `Unknown { tag: u8, bytes: Vec<u8> }`, as `#[serbytes(other)]`.
+/// It's read for every unknown ordinal, keeping the ordinal and the raw payload so they're written back unchanged
+/// The id field is an unsigned integer no wider than the tag.
+/// Delimited enums can't use `tag = bits`, as its width changes when variants are added
+///
+/// The ordinal of a variant is its discriminant, so reordering variants with explicit discriminants
+/// doesn't change their encoding. `#[serbytes(id = N)]` on a variant sets its ordinal instead, ordinals
+/// may have gaps but can't be used twice
+///
+/// `#[serbytes(borrow)]` on a type with one lifetime parameter, i.e. a struct with a `&'a str` field, derives
+/// `SerBytesBorrow<'a>` instead, with its `Cow<str>` and `Cow<[u8]>` fields borrowing from the input too.
+/// Without it, types with lifetimes derive `SerBytes` like any other type
+///
+/// Fields accept these attributes:
+/// - `#[serbytes(skip)]` neither reads nor writes the field, which is set to `Default::default()` when read
+/// - `#[serbytes(skip, default = "path")]` sets a skipped field to the result of calling `path` instead
+/// - `#[serbytes(with = "module")]` reads and writes the field with `module::value_from_buf` and
+///   `module::try_value_to_buf`, which have the same signatures as those of `MappedDataProvider`. A type
+///   implementing `MappedDataProvider` for the field can be given instead, its `try_value_to_buf` defaults to
+///   calling `value_to_buf`. The size of these fields isn't known, so they don't count towards `approx_size`
+/// - `#[serbytes(len = u32)]` writes the length of a `LengthPrefixed` field, i.e. a `Vec` or `String`, as the given `LengthLike`
+///
+/// `#[serbytes(version = N)]` on a struct makes N the current version of its format and also derives
+/// `SerBytesVersioned`, which reads the fields written by an older version. The version is taken from the
+/// header of a `Versioned`, or passed to `from_buf_versioned` by the `CurrentVersion` of a `VersioningWrapper`.
+/// Its fields then accept:
+/// - `#[serbytes(since = 3)]` on a field added in version 3, which is defaulted when reading earlier versions
+/// - `#[serbytes(until = 5)]` on a field removed in version 5, which is only read from earlier versions and
+///   defaulted otherwise. It's never written once the current version reaches 5
+/// - `#[serbytes(since = 3, default = "path")]` defaults the field to the result of calling `path` instead
+///
+/// The current version is always written, reading data from a newer version than the current one is an error
+#[proc_macro_derive(SerBytes, attributes(serbytes))]
 pub fn derive_ser_bytes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     ser_derive(input)
 }
@@ -34,12 +34,18 @@ where
     pub fn len(&self) -> usize {
         self.len
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::prelude::index_pointer::write::IndexPointerWrite;
-    use bytebuffer::prelude::{ReadByteBufferOwned, WriteByteBufferOwned};
+    use bytebuffer::prelude::{
+        ReadByteBuffer, ReadByteBufferOwned, WriteByteBuffer, WriteByteBufferOwned,
+    };
 
     #[test]
     fn test_index_pointer() {
@@ -4,10 +4,9 @@ use bytebuffer::prelude::WriteByteBufferOwned;
 
 pub trait IndexPointerWrite {
     /// Write the Ser type to the buffer and returns an [`IndexPointer`] at the location where the type was written.
-
+    ///
     /// We don't need to restrict this method to [`SerBytesStaticSized`] because we don't care about the length of the content written as it's at the end of the buffer anyways.
     /// We only care when trying to write the data back at the [`IndexPointer`]
-
     fn write_with_index_pointer<S>(&mut self, val: &S) -> IndexPointer<S>
     where
         S: SerBytes;
@@ -16,7 +15,6 @@ pub trait IndexPointerWrite {
     /// use the method [`WriteByteBufferOwned::try_write_at_index_pointer`]
     ///
     /// This function will not fail so long as all types which implement [`SerBytesStaticSized`] adhere to it's rules
-
     fn write_at_index_pointer<S>(&mut self, index_pointer: IndexPointer<S>, val: &S)
     where
         S: SerBytesStaticSized;
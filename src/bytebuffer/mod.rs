@@ -2,3 +2,4 @@ pub mod index_pointer;
 pub mod read;
 
 pub use index_pointer::*;
+pub use read::*;
@@ -1,35 +1,60 @@
-use crate::bytebuffer::IndexPointer;
-use crate::bytebuffer::index_pointer::write::IndexPointerWrite;
-use crate::ser_bytes_impl::from_buf;
-use crate::ser_trait::SerBytes;
+mod read_all;
+
+pub use read_all::*;
+
+use crate::ser_bytes_impl::{
+    expect_written, from_buf, from_buf_nested, length_from_buf, length_to_buf,
+};
+use crate::ser_trait::{SerBytes, SerBytesBorrow};
 use bytebuffer::prelude::{
-    BBReadResult, ReadByteBufferRefMut, ReadError, SpecificError, WriteByteBufferOwned,
+    BBReadResult, BBWriteResult, DecodeLimit, ErrorPath, PathSegment, ReadByteBuffer, ReadError,
+    ReadPosition, SpecificError, WriteByteBuffer,
 };
+use std::error::Error;
+use std::sync::Arc;
 
-impl<'s> SerBytes for ReadError<'s> {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+impl SerBytes for ReadError {
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
-        let specific_error = from_buf(buf)?;
-        let of = String::from_buf(buf)?;
-        let child = from_buf(buf)?;
+        // Errors are recursive through their child
+        from_buf_nested(buf, |buf| {
+            let specific_error = from_buf(buf)?;
+            let of = String::from_buf(buf)?;
+            let child = from_buf(buf)?;
+            let position = from_buf(buf)?;
+            let segments = from_buf(buf)?;
 
-        Ok(Self::new(specific_error, of, child))
+            Ok(Self {
+                position,
+                path: ErrorPath { segments },
+                ..Self::new(specific_error, of, child)
+            })
+        })
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        self.specific_error.to_buf(buf);
-        self.of.to_buf(buf);
-        self.child.to_buf(buf);
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
+
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        self.specific_error.try_to_buf(buf)?;
+        self.of.try_to_buf_borrowed(buf)?;
+        self.child.try_to_buf(buf)?;
+        self.position.try_to_buf(buf)?;
+        self.path.segments.try_to_buf(buf)
     }
 }
 
-impl<'s> SerBytes for SpecificError<'s> {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+impl SerBytes for SpecificError {
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
+        let start = buf.checkpoint();
         let ordinal = buf.read_u8()?;
 
         let s = match ordinal {
@@ -45,52 +70,268 @@ impl<'s> SerBytes for SpecificError<'s> {
                 got: from_buf(buf)?,
             },
             5 => Self::InvalidEnum,
-            6 => Self::Other(from_buf(buf)?),
+            6 => Self::Other(String::from_buf(buf)?.into()),
+            7 => Self::VarintOverflow,
+            8 => Self::LimitExceeded {
+                limit: from_buf(buf)?,
+                max: from_buf(buf)?,
+                got: from_buf(buf)?,
+            },
+            9 => Self::InvalidUtf8,
+            10 => Self::UnexpectedEof,
+            11 => Self::LengthLimit,
+            12 => Self::InvalidBool(from_buf(buf)?),
+            13 => Self::TrailingBytes(from_buf(buf)?),
+            // Only the message of a custom error is written
+            14 => Self::Custom(Arc::from(Box::<dyn Error + Send + Sync>::from(
+                String::from_buf(buf)?,
+            ))),
+            15 => Self::InvalidBitCount {
+                max: from_buf(buf)?,
+                got: length_from_buf::<u64>(buf)?,
+            },
+            16 => Self::UnknownVersion {
+                current: from_buf(buf)?,
+                got: from_buf(buf)?,
+            },
+            17 => Self::NoProgress,
             _ => {
                 return Err(ReadError::new(
                     SpecificError::EnumOrdinalOutOfBounds {
-                        max_bound: 5,
-                        got: ordinal,
+                        max_bound: 17,
+                        got: ordinal.into(),
                     },
                     "SpecificError",
                     None,
-                ));
+                )
+                .at(start));
             }
         };
 
         Ok(s)
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        let ord_ip: IndexPointer<u8> = buf.write_with_index_pointer(&0);
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
 
-        let ord = match self {
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        let ord: u8 = match self {
             Self::U8 => 0,
+            Self::Bytes { .. } => 1,
+            Self::SingleBit => 2,
+            Self::RemainingBits => 3,
+            Self::EnumOrdinalOutOfBounds { .. } => 4,
+            Self::InvalidEnum => 5,
+            Self::Other(_) => 6,
+            Self::VarintOverflow => 7,
+            Self::LimitExceeded { .. } => 8,
+            Self::InvalidUtf8 => 9,
+            Self::UnexpectedEof => 10,
+            Self::LengthLimit => 11,
+            Self::InvalidBool(_) => 12,
+            Self::TrailingBytes(_) => 13,
+            Self::Custom(_) => 14,
+            Self::InvalidBitCount { .. } => 15,
+            Self::UnknownVersion { .. } => 16,
+            Self::NoProgress => 17,
+        };
+
+        ord.to_buf(buf);
+
+        match self {
             Self::Bytes {
                 remaining_bytes,
                 got,
             } => {
                 remaining_bytes.to_buf(buf);
                 got.to_buf(buf);
-
-                1
             }
-            Self::SingleBit => 2,
-            Self::RemainingBits => 3,
             Self::EnumOrdinalOutOfBounds { got, max_bound } => {
                 max_bound.to_buf(buf);
                 got.to_buf(buf);
-
-                4
             }
-            Self::InvalidEnum => 5,
             Self::Other(other_str) => {
-                other_str.to_buf(buf);
+                other_str.try_to_buf_borrowed(buf)?;
+            }
+            Self::LimitExceeded { limit, max, got } => {
+                limit.try_to_buf(buf)?;
+                max.try_to_buf(buf)?;
+                got.try_to_buf(buf)?;
+            }
+            Self::InvalidBool(got) => {
+                got.try_to_buf(buf)?;
+            }
+            Self::TrailingBytes(remaining) => {
+                remaining.try_to_buf(buf)?;
+            }
+            Self::Custom(err) => {
+                err.to_string().try_to_buf(buf)?;
+            }
+            Self::InvalidBitCount { max, got } => {
+                max.try_to_buf(buf)?;
+                length_to_buf::<u64>(buf, *got)?;
+            }
+            Self::UnknownVersion { current, got } => {
+                current.try_to_buf(buf)?;
+                got.try_to_buf(buf)?;
+            }
+            Self::U8
+            | Self::SingleBit
+            | Self::RemainingBits
+            | Self::InvalidEnum
+            | Self::VarintOverflow
+            | Self::InvalidUtf8
+            | Self::UnexpectedEof
+            | Self::LengthLimit
+            | Self::NoProgress => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl SerBytes for PathSegment {
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+        Self: Sized,
+    {
+        let start = buf.checkpoint();
+        let ordinal = buf.read_u8()?;
+
+        let segment = match ordinal {
+            0 => Self::Type(String::from_buf(buf)?.into()),
+            1 => Self::Field(String::from_buf(buf)?.into()),
+            2 => Self::Variant(String::from_buf(buf)?.into()),
+            3 => Self::Index(length_from_buf::<u64>(buf)?),
+            _ => {
+                return Err(ReadError::new(
+                    SpecificError::EnumOrdinalOutOfBounds {
+                        max_bound: 3,
+                        got: ordinal.into(),
+                    },
+                    "PathSegment",
+                    None,
+                )
+                .at(start));
+            }
+        };
+
+        Ok(segment)
+    }
 
-                6
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
+
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        match self {
+            Self::Type(name) => {
+                0u8.to_buf(buf);
+                name.try_to_buf_borrowed(buf)
+            }
+            Self::Field(name) => {
+                1u8.to_buf(buf);
+                name.try_to_buf_borrowed(buf)
+            }
+            Self::Variant(name) => {
+                2u8.to_buf(buf);
+                name.try_to_buf_borrowed(buf)
+            }
+            Self::Index(index) => {
+                3u8.to_buf(buf);
+                length_to_buf::<u64>(buf, *index)
+            }
+        }
+    }
+}
+
+impl SerBytes for ReadPosition {
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+        Self: Sized,
+    {
+        let byte = u64::from_buf(buf)?;
+        let bit = u8::from_buf(buf)?;
+
+        let byte = usize::try_from(byte).map_err(|_| {
+            ReadError::new(SpecificError::LengthLimit, "ReadPosition", None).at(buf.checkpoint())
+        })?;
+
+        Ok(Self {
+            byte,
+            bit: bit as usize,
+        })
+    }
+
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        (self.byte as u64).to_buf(buf);
+        (self.bit as u8).to_buf(buf);
+    }
+
+    fn size_hint() -> usize
+    where
+        Self: Sized,
+    {
+        u64::size_hint() + u8::size_hint()
+    }
+
+    fn approx_size(&self) -> usize {
+        Self::size_hint()
+    }
+}
+
+impl SerBytes for DecodeLimit {
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+        Self: Sized,
+    {
+        let start = buf.checkpoint();
+        let ordinal = buf.read_u8()?;
+
+        let limit = match ordinal {
+            0 => Self::Alloc,
+            1 => Self::CollectionLen,
+            2 => Self::StringLen,
+            3 => Self::Depth,
+            _ => {
+                return Err(ReadError::new(
+                    SpecificError::EnumOrdinalOutOfBounds {
+                        max_bound: 3,
+                        got: ordinal.into(),
+                    },
+                    "DecodeLimit",
+                    None,
+                )
+                .at(start));
             }
         };
 
-        buf.write_at_index_pointer(ord_ip, &ord);
+        Ok(limit)
+    }
+
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        let ord: u8 = match self {
+            Self::Alloc => 0,
+            Self::CollectionLen => 1,
+            Self::StringLen => 2,
+            Self::Depth => 3,
+        };
+
+        ord.to_buf(buf);
+    }
+
+    fn size_hint() -> usize
+    where
+        Self: Sized,
+    {
+        u8::size_hint()
+    }
+
+    fn approx_size(&self) -> usize {
+        u8::size_hint()
     }
 }
@@ -0,0 +1,85 @@
+use crate::ser_trait::SerBytes;
+use bytebuffer::prelude::{BBReadResult, ReadByteBuffer, ReadError, SpecificError};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+/// Decoding consecutive values from a reader, see [`ReadSerBytes::read_all`]
+pub trait ReadSerBytes: ReadByteBuffer {
+    /// An iterator decoding consecutive `S`s until every whole byte has been read
+    ///
+    /// Stops after the first error, which includes input ending part way through a value. Types which
+    /// are read without using any input, i.e. `()`, error with [`SpecificError::NoProgress`] as they'd be read forever
+    ///
+    /// The reader is [released](ReadByteBuffer::release) before each value, so streams only hold on to the
+    /// value being read and checkpoints taken beforehand can't be restored
+    fn read_all<S: SerBytes>(&mut self) -> ReadAll<'_, Self, S> {
+        ReadAll {
+            buf: self,
+            done: false,
+            _item: PhantomData,
+        }
+    }
+}
+
+impl<R> ReadSerBytes for R where R: ReadByteBuffer + ?Sized {}
+
+/// See [`ReadSerBytes::read_all`]
+pub struct ReadAll<'a, R: ?Sized, S> {
+    buf: &'a mut R,
+    done: bool,
+    _item: PhantomData<fn() -> S>,
+}
+
+impl<R, S> Iterator for ReadAll<'_, R, S>
+where
+    R: ReadByteBuffer + ?Sized,
+    S: SerBytes,
+{
+    type Item = BBReadResult<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        self.buf.release();
+
+        let result = match self.buf.at_end() {
+            Ok(true) => {
+                self.done = true;
+                return None;
+            }
+            Ok(false) => self.read_next(),
+            Err(err) => Err(err),
+        };
+
+        self.done = result.is_err();
+
+        Some(result)
+    }
+}
+
+impl<R, S> ReadAll<'_, R, S>
+where
+    R: ReadByteBuffer + ?Sized,
+    S: SerBytes,
+{
+    fn read_next(&mut self) -> BBReadResult<S> {
+        let start = self.buf.checkpoint();
+        let value = S::from_buf(self.buf)?;
+
+        // A value taking up no space would otherwise be read forever
+        if self.buf.checkpoint() == start {
+            return Err(ReadError::new(SpecificError::NoProgress, "ReadAll", None).at(start));
+        }
+
+        Ok(value)
+    }
+}
+
+impl<R, S> FusedIterator for ReadAll<'_, R, S>
+where
+    R: ReadByteBuffer + ?Sized,
+    S: SerBytes,
+{
+}
@@ -1,25 +1,23 @@
-use crate::bytebuffer::ReadError;
 use crate::ser_trait::SerBytes;
+use bytebuffer::prelude::ReadError;
+use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::io;
 use std::path::Path;
 
-pub type FromFileResult<'a, T> = Result<T, FromFileError<'a>>;
+pub type FromFileResult<T> = Result<T, FromFileError>;
 
 /// Error returned when trying to read [`SerBytes`] data from a file, it has 2 single tuple variants:
 /// Read error with a [`ReadError`] and IOError with an [`io::Error`]
-///
-/// Check [`ReadError`] for information on the lifetime
 #[derive(Debug)]
-pub enum FromFileError<'a> {
-    ReadError(ReadError<'a>),
+pub enum FromFileError {
+    ReadError(ReadError),
     IOError(io::Error),
 }
 
 /// Trait relating to filesystem operations on types that implement [`SerBytes`]
-
 pub trait SerBytesFs: SerBytes {
-    fn from_file_path<'a>(path: impl AsRef<Path>) -> FromFileResult<'a, Self>
+    fn from_file_path(path: impl AsRef<Path>) -> FromFileResult<Self>
     where
         Self: Sized;
 
@@ -35,15 +33,14 @@ where
     /// Errors if it was unable to read bytes from the file.
     ///
     /// Errors if deserialization fails.
-
-    fn from_file_path<'a>(path: impl AsRef<Path>) -> FromFileResult<'a, Self>
+    fn from_file_path(path: impl AsRef<Path>) -> FromFileResult<Self>
     where
         Self: Sized,
     {
         use std::fs;
         let buf = fs::read(path)?;
 
-        Self::from_vec(buf).map_err(|read_error| FromFileError::ReadError(read_error))
+        Self::from_vec(buf).map_err(FromFileError::ReadError)
     }
 
     /// Serializes and writes data to a given file path.
@@ -66,13 +63,13 @@ where
             fs::create_dir_all(parent_dir)?;
         }
 
-        let wbb = self.to_bb();
+        let wbb = self.to_bb()?;
 
         fs::write(path, wbb.buf())
     }
 }
 
-impl<'a> Display for FromFileError<'a> {
+impl Display for FromFileError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::ReadError(err) => {
@@ -86,14 +83,23 @@ impl<'a> Display for FromFileError<'a> {
     }
 }
 
-impl<'a> From<io::Error> for FromFileError<'a> {
+impl Error for FromFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ReadError(err) => Some(err),
+            Self::IOError(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for FromFileError {
     fn from(value: io::Error) -> Self {
         Self::IOError(value)
     }
 }
 
-impl<'a> From<ReadError<'a>> for FromFileError<'a> {
-    fn from(value: ReadError<'a>) -> Self {
+impl From<ReadError> for FromFileError {
+    fn from(value: ReadError) -> Self {
         Self::ReadError(value)
     }
 }
@@ -1,12 +1,13 @@
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WriteByteBufferOwned};
+use bytebuffer::prelude::{BBReadResult, ReadByteBuffer, WriteByteBuffer};
 
 pub trait SerbytesMapped<T> {
-    fn from_buf_mapped<F>(buf: &mut ReadByteBufferRefMut, reader: F) -> BBReadResult<Self>
+    fn from_buf_mapped<R, F>(buf: &mut R, reader: F) -> BBReadResult<Self>
     where
         Self: Sized,
-        F: FnOnce(&mut ReadByteBufferRefMut) -> BBReadResult<T>;
+        R: ReadByteBuffer + ?Sized,
+        F: FnOnce(&mut R) -> BBReadResult<T>;
 
-    fn to_buf_mapped<F>(&self, buf: &mut WriteByteBufferOwned, writer: F)
+    fn to_buf_mapped<F>(&self, buf: &mut dyn WriteByteBuffer, writer: F)
     where
-        F: FnOnce(&mut WriteByteBufferOwned, &T);
+        F: FnOnce(&mut dyn WriteByteBuffer, &T);
 }
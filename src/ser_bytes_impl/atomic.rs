@@ -1,16 +1,17 @@
 use crate::ser_trait::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WriteByteBufferOwned};
+use bytebuffer::prelude::{BBReadResult, ReadByteBuffer, WriteByteBuffer};
 use std::sync::atomic::{AtomicU64, Ordering};
 
 impl SerBytes for AtomicU64 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         Ok(buf.read_u64()?.into())
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
         self.load(Ordering::Relaxed).to_buf(buf);
     }
 }
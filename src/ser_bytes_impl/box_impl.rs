@@ -1,18 +1,28 @@
 use crate::prelude::{SerBytes, SerBytesStaticSized};
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WriteByteBufferOwned};
+use crate::ser_bytes_impl::{
+    LengthPrefixed, expect_written, prefixed_approx_size, prefixed_from_buf, prefixed_to_buf,
+};
+use bytebuffer::prelude::{BBReadResult, BBWriteResult, ReadByteBuffer, WriteByteBuffer};
 
 impl<S> SerBytes for Box<S>
 where
     S: SerBytes,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self> {
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
         Ok(Self::new(S::from_buf(buf)?))
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
         S::to_buf(self, buf);
     }
 
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        S::try_to_buf(self, buf)
+    }
+
     fn size_hint() -> usize
     where
         Self: Sized,
@@ -21,7 +31,7 @@ where
     }
 
     fn approx_size(&self) -> usize {
-        S::approx_size(&self)
+        S::approx_size(self)
     }
 }
 
@@ -31,22 +41,58 @@ impl<S> SerBytes for Box<[S]>
 where
     S: SerBytes,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
-        let v = Vec::from_buf(buf)?;
+        prefixed_from_buf::<Self, u16>(buf)
+    }
 
-        Ok(v.into_boxed_slice())
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        let len = self.len() as u16;
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        prefixed_to_buf::<Self, u16>(buf, self)
+    }
 
-        len.to_buf(buf);
+    fn size_hint() -> usize
+    where
+        Self: Sized,
+    {
+        u16::size_hint()
+    }
 
+    fn approx_size(&self) -> usize {
+        prefixed_approx_size::<Self, u16>(self)
+    }
+}
+
+impl<S> LengthPrefixed for Box<[S]>
+where
+    S: SerBytes,
+{
+    fn items_len(&self) -> usize {
+        self.len()
+    }
+
+    fn items_from_buf<R>(buf: &mut R, len: usize) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        Vec::<S>::items_from_buf(buf, len).map(Vec::into_boxed_slice)
+    }
+
+    fn items_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
         for s in self {
-            s.to_buf(buf);
+            s.try_to_buf(buf)?;
         }
+
+        Ok(())
+    }
+
+    fn items_approx_size(&self) -> usize {
+        S::size_hint() * self.len()
     }
 }
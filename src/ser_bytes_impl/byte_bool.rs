@@ -0,0 +1,60 @@
+use crate::ser_trait::{SerBytes, SerBytesStaticSized};
+use bytebuffer::prelude::{BBReadResult, BBWriteResult, ReadByteBuffer, WriteByteBuffer};
+
+/// A bool stored as a whole byte rather than a single bit, for formats which lay out bools that way
+///
+/// Reading errors with [`SpecificError::InvalidBool`](bytebuffer::prelude::SpecificError::InvalidBool) if the byte isn't 0 or 1
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ByteBool(pub bool);
+
+impl From<bool> for ByteBool {
+    fn from(value: bool) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ByteBool> for bool {
+    fn from(value: ByteBool) -> Self {
+        value.0
+    }
+}
+
+impl SerBytes for ByteBool {
+    #[inline(always)]
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        buf.read_byte_bool().map(Self)
+    }
+
+    #[inline(always)]
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        buf.write_byte_bool(self.0);
+    }
+
+    #[inline(always)]
+    fn to_buf_with<W>(&self, buf: &mut W) -> BBWriteResult<()>
+    where
+        W: WriteByteBuffer,
+    {
+        buf.write_byte_bool(self.0);
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn size_hint() -> usize
+    where
+        Self: Sized,
+    {
+        1
+    }
+
+    #[inline(always)]
+    fn approx_size(&self) -> usize {
+        1
+    }
+}
+
+impl SerBytesStaticSized for ByteBool {}
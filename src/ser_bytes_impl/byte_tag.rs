@@ -1,7 +1,11 @@
 use crate::bytebuffer::index_pointer::IndexPointer;
 use crate::bytebuffer::index_pointer::write::IndexPointerWrite;
+use crate::ser_bytes_impl::preallocation;
 use crate::ser_trait::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WriteByteBufferOwned};
+use bytebuffer::prelude::{
+    BBReadResult, ReadByteBuffer, ReadByteBufferRefMut, WithPath, WriteByteBuffer,
+    WriteByteBufferOwned,
+};
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::marker::PhantomData;
@@ -51,6 +55,16 @@ where
     }
 }
 
+impl<K, V> Default for WriteSerByteTag<K, V>
+where
+    K: SerBytes,
+    V: SerBytes,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct ReadSerByteTag<K, V> {
     tags: HashMap<K, V>,
 }
@@ -63,11 +77,13 @@ where
     pub fn from_buf(mut rbb: ReadByteBufferRefMut) -> BBReadResult<Self> {
         let len = u16::from_buf(&mut rbb)?;
 
-        let mut tags = HashMap::with_capacity(len as usize);
+        rbb.claim_collection(len as usize, size_of::<(K, V)>())?;
+
+        let mut tags = HashMap::with_capacity(preallocation::<(K, V)>(len as usize));
 
-        for _ in 0..len {
-            let key = K::from_buf(&mut rbb)?;
-            let value = V::from_buf(&mut rbb)?;
+        for i in 0..len as usize {
+            let key = K::from_buf(&mut rbb).at_index(i)?;
+            let value = V::from_buf(&mut rbb).at_index(i)?;
 
             tags.insert(key, value);
         }
@@ -1,5 +1,11 @@
 use crate::prelude::{SerBytes, from_buf};
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WithParent, WriteByteBufferOwned};
+use crate::ser_bytes_impl::{
+    LengthPrefixed, expect_written, preallocation, prefixed_approx_size, prefixed_from_buf,
+    prefixed_to_buf,
+};
+use bytebuffer::prelude::{
+    BBReadResult, BBWriteResult, ReadByteBuffer, WithParent, WithPath, WriteByteBuffer,
+};
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 
@@ -8,35 +14,21 @@ where
     K: SerBytes + Eq + Hash,
     V: SerBytes,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self> {
-        let mut inner = || {
-            let len = u16::from_buf(buf)? as usize;
-            let mut map = Self::with_capacity(len);
-
-            for _ in 0..len {
-                let key = from_buf(buf)?;
-                let value = from_buf(buf)?;
-
-                map.insert(key, value);
-            }
-
-            Ok(map)
-        };
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        let mut inner = || prefixed_from_buf::<Self, u16>(buf);
 
         inner().with_parent("HashMap")
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        let len = self.len() as u16;
-
-        buf.reserve(self.approx_size());
-
-        len.to_buf(buf);
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
 
-        for (key, value) in self {
-            key.to_buf(buf);
-            value.to_buf(buf);
-        }
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        prefixed_to_buf::<Self, u16>(buf, self)
     }
 
     fn size_hint() -> usize
@@ -47,34 +39,70 @@ where
     }
 
     fn approx_size(&self) -> usize {
-        u16::size_hint() + ((K::size_hint() + V::size_hint()) * self.len())
+        prefixed_approx_size::<Self, u16>(self)
     }
 }
 
-impl<K> SerBytes for HashSet<K>
+impl<K, V> LengthPrefixed for HashMap<K, V>
 where
     K: SerBytes + Eq + Hash,
+    V: SerBytes,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self> {
-        let mut inner = || {
-            let len = u16::from_buf(buf)?;
-            let mut set = HashSet::with_capacity(len as usize);
+    fn items_len(&self) -> usize {
+        self.len()
+    }
 
-            for _ in 0..len {
-                set.insert(from_buf(buf)?);
-            }
+    fn items_from_buf<R>(buf: &mut R, len: usize) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        buf.claim_collection(len, size_of::<(K, V)>())?;
 
-            Ok(set)
-        };
+        let mut map = Self::with_capacity(preallocation::<(K, V)>(len));
 
-        inner().with_parent("HashSet")
+        for i in 0..len {
+            let key = from_buf(buf).at_index(i)?;
+            let value = from_buf(buf).at_index(i)?;
+
+            map.insert(key, value);
+        }
+
+        Ok(map)
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        (self.len() as u16).to_buf(buf);
-        for key in self.iter() {
-            key.to_buf(buf);
+    fn items_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        for (key, value) in self {
+            key.try_to_buf(buf)?;
+            value.try_to_buf(buf)?;
         }
+
+        Ok(())
+    }
+
+    fn items_approx_size(&self) -> usize {
+        (K::size_hint() + V::size_hint()) * self.len()
+    }
+}
+
+impl<K> SerBytes for HashSet<K>
+where
+    K: SerBytes + Eq + Hash,
+{
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        let mut inner = || prefixed_from_buf::<Self, u16>(buf);
+
+        inner().with_parent("HashSet")
+    }
+
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
+
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        prefixed_to_buf::<Self, u16>(buf, self)
     }
 
     fn size_hint() -> usize
@@ -85,6 +113,42 @@ where
     }
 
     fn approx_size(&self) -> usize {
-        u16::size_hint() + (K::size_hint() * self.len())
+        prefixed_approx_size::<Self, u16>(self)
+    }
+}
+
+impl<K> LengthPrefixed for HashSet<K>
+where
+    K: SerBytes + Eq + Hash,
+{
+    fn items_len(&self) -> usize {
+        self.len()
+    }
+
+    fn items_from_buf<R>(buf: &mut R, len: usize) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        buf.claim_collection(len, size_of::<K>())?;
+
+        let mut set = Self::with_capacity(preallocation::<K>(len));
+
+        for i in 0..len {
+            set.insert(from_buf(buf).at_index(i)?);
+        }
+
+        Ok(set)
+    }
+
+    fn items_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        for key in self {
+            key.try_to_buf(buf)?;
+        }
+
+        Ok(())
+    }
+
+    fn items_approx_size(&self) -> usize {
+        K::size_hint() * self.len()
     }
 }
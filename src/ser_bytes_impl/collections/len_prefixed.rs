@@ -0,0 +1,128 @@
+use crate::ser_bytes_impl::{LengthLike, expect_written, length_from_buf, length_to_buf};
+use crate::ser_trait::SerBytes;
+use bytebuffer::prelude::{
+    BBReadResult, BBWriteResult, ReadByteBuffer, WithParent, WriteByteBuffer,
+};
+use std::marker::PhantomData;
+
+/// A collection which is encoded as its length followed by each of its items
+///
+/// The [`SerBytes`] impls of these collections use a `u16` length, wrap them in a [`LenPrefixed`] to
+/// use a different [`LengthLike`] type
+pub trait LengthPrefixed: Sized {
+    /// The length written before the items
+    fn items_len(&self) -> usize;
+
+    /// Reads `len` items from the buffer
+    fn items_from_buf<R>(buf: &mut R, len: usize) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized;
+
+    fn items_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()>;
+
+    /// The approximate size of the items, excluding the length
+    fn items_approx_size(&self) -> usize;
+}
+
+pub fn prefixed_from_buf<C, L>(buf: &mut (impl ReadByteBuffer + ?Sized)) -> BBReadResult<C>
+where
+    C: LengthPrefixed,
+    L: LengthLike,
+{
+    let len = length_from_buf::<L>(buf)?;
+
+    C::items_from_buf(buf, len)
+}
+
+/// Errors if the length of the collection doesn't fit into `L`
+pub fn prefixed_to_buf<C, L>(buf: &mut dyn WriteByteBuffer, collection: &C) -> BBWriteResult<()>
+where
+    C: LengthPrefixed,
+    L: LengthLike,
+{
+    buf.reserve(prefixed_approx_size::<C, L>(collection));
+
+    length_to_buf::<L>(buf, collection.items_len())?;
+
+    collection.items_to_buf(buf)
+}
+
+#[inline(always)]
+pub fn prefixed_approx_size<C, L>(collection: &C) -> usize
+where
+    C: LengthPrefixed,
+    L: LengthLike,
+{
+    L::size_hint() + collection.items_approx_size()
+}
+
+/// A collection with its length encoded as `L` instead of a `u16`, i.e. `LenPrefixed<Vec<u64>, u32>`
+/// or `LenPrefixed<String, Size>`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct LenPrefixed<C, L = u16> {
+    pub inner: C,
+    _len: PhantomData<L>,
+}
+
+impl<C, L> LenPrefixed<C, L> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            _len: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C, L> SerBytes for LenPrefixed<C, L>
+where
+    C: LengthPrefixed,
+    L: LengthLike,
+{
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+        Self: Sized,
+    {
+        let mut inner = || prefixed_from_buf::<C, L>(buf).map(Self::new);
+
+        inner().with_parent("LenPrefixed")
+    }
+
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
+
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        prefixed_to_buf::<C, L>(buf, &self.inner)
+    }
+
+    fn size_hint() -> usize
+    where
+        Self: Sized,
+    {
+        L::size_hint()
+    }
+
+    fn approx_size(&self) -> usize {
+        prefixed_approx_size::<C, L>(&self.inner)
+    }
+}
+
+impl<C, L> Default for LenPrefixed<C, L>
+where
+    C: Default,
+{
+    fn default() -> Self {
+        Self::new(C::default())
+    }
+}
+
+impl<C, L> From<C> for LenPrefixed<C, L> {
+    fn from(value: C) -> Self {
+        Self::new(value)
+    }
+}
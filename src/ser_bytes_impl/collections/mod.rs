@@ -1,5 +1,7 @@
 pub mod hashmap;
+pub mod len_prefixed;
 pub mod string;
 pub mod vec_like;
 
+pub use len_prefixed::*;
 pub use vec_like::*;
@@ -1,31 +1,43 @@
-use crate::prelude::SerBytes;
-use crate::ser_bytes_impl::U8Vec;
+use crate::prelude::{SerBytes, SerBytesBorrow};
+use crate::ser_bytes_impl::{
+    LengthPrefixed, expect_written, from_buf, prefixed_approx_size, prefixed_from_buf,
+    prefixed_to_buf, u8_slice_to_buf,
+};
 use bytebuffer::prelude::{
-    BBReadResult, ReadByteBufferRefMut, ReadError, SpecificError, WithParent, WriteByteBufferOwned,
+    BBReadResult, BBWriteResult, ReadByteBuffer, ReadByteBufferSlice, ReadError, ReadPosition,
+    SpecificError, WithParent, WriteByteBuffer,
 };
+use std::str::Utf8Error;
+
+/// Errors at the first invalid byte of the `len` bytes which were just read
+fn invalid_utf8<R>(buf: &R, len: usize, err: Utf8Error) -> ReadError
+where
+    R: ReadByteBuffer + ?Sized,
+{
+    let end = ReadPosition::from(buf.checkpoint());
+
+    ReadError::new(SpecificError::InvalidUtf8, "Validate utf8", None).at(ReadPosition {
+        byte: end.byte.saturating_sub(len) + err.valid_up_to(),
+        ..end
+    })
+}
 
 impl SerBytes for String {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self> {
-        let mut inner = || {
-            let u8_vec = U8Vec::<u16>::from_buf(buf)?;
-
-            String::from_utf8(u8_vec.vec).map_err(|_| {
-                ReadError::new(
-                    SpecificError::Other("Invalid utf8".into()),
-                    "Validate utf8",
-                    None,
-                )
-            })
-        };
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        let mut inner = || prefixed_from_buf::<Self, u16>(buf);
 
         inner().with_parent("String")
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        buf.reserve(self.approx_size());
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
 
-        buf.write_u16(self.len() as u16);
-        buf.write_bytes(self.as_bytes());
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        prefixed_to_buf::<Self, u16>(buf, self)
     }
 
     fn size_hint() -> usize
@@ -36,6 +48,63 @@ impl SerBytes for String {
     }
 
     fn approx_size(&self) -> usize {
+        prefixed_approx_size::<Self, u16>(self)
+    }
+}
+
+/// The length of a [`String`] is its length in bytes
+impl LengthPrefixed for String {
+    fn items_len(&self) -> usize {
+        self.len()
+    }
+
+    fn items_from_buf<R>(buf: &mut R, len: usize) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        buf.claim_string(len)?;
+
+        let bytes = buf.read_bytes(len)?.to_vec();
+
+        String::from_utf8(bytes).map_err(|err| invalid_utf8(buf, len, err.utf8_error()))
+    }
+
+    fn items_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        buf.write_bytes(self.as_bytes());
+
+        Ok(())
+    }
+
+    fn items_approx_size(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<'de> SerBytesBorrow<'de> for &'de str {
+    fn from_buf_borrowed(buf: &mut ReadByteBufferSlice<'de>) -> BBReadResult<Self> {
+        let mut inner = || {
+            let len = from_buf::<u16>(buf)? as usize;
+            buf.decode_budget()
+                .check_string_len(len)
+                .map_err(|err| err.at(buf.checkpoint()))?;
+
+            let bytes = buf.read_borrowed_bytes(len)?;
+
+            str::from_utf8(bytes).map_err(|err| invalid_utf8(buf, len, err))
+        };
+
+        inner().with_parent("&str")
+    }
+
+    fn to_buf_borrowed(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf_borrowed(buf));
+    }
+
+    fn try_to_buf_borrowed(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        u8_slice_to_buf::<u16>(buf, self.as_bytes())
+    }
+
+    fn approx_size_borrowed(&self) -> usize {
         u16::size_hint() + self.len()
     }
 }
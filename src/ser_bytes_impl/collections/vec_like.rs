@@ -1,6 +1,12 @@
-use crate::ser_bytes_impl::{LengthLike, slice_to_buf_u16, u8_slice_to_buf, vec_from_buf_u16};
-use crate::ser_trait::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WithParent, WriteByteBufferOwned};
+use crate::ser_bytes_impl::{
+    LengthLike, LengthPrefixed, expect_written, from_buf, length_from_buf, length_to_buf,
+    preallocation, prefixed_approx_size, prefixed_from_buf, prefixed_to_buf, u8_slice_to_buf,
+};
+use crate::ser_trait::{SerBytes, SerBytesBorrow};
+use bytebuffer::prelude::{
+    BBReadResult, BBWriteResult, ReadByteBuffer, ReadByteBufferSlice, WithParent, WithPath,
+    WriteByteBuffer,
+};
 use std::collections::VecDeque;
 use std::marker::PhantomData;
 
@@ -10,17 +16,40 @@ where
 {
     /// Reads bytes from a buffer into a [`Vec<S>`]
     ///
-    /// Uses a `u16` to determine the amount of bytes to read
+    /// Uses a `u16` to determine the amount of bytes to read, use [`LenPrefixed`](crate::prelude::LenPrefixed) for a different length type
     ///
     /// NOTE: This is incredibly inefficient for a [`Vec`] of `u8` or `i8`, instead use [`U8Vec`]
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self> {
-        let mut inner = || vec_from_buf_u16(buf);
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        let mut inner = || prefixed_from_buf::<Self, u16>(buf);
 
         inner().with_parent("Vec")
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        slice_to_buf_u16(buf, self);
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
+
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        prefixed_to_buf::<Self, u16>(buf, self)
+    }
+
+    /// Writes the items with [`SerBytes::to_buf_with`], so a [`Vec`] of primitives makes no dynamic calls per item
+    fn to_buf_with<W>(&self, buf: &mut W) -> BBWriteResult<()>
+    where
+        W: WriteByteBuffer,
+    {
+        buf.reserve(prefixed_approx_size::<Self, u16>(self));
+
+        length_to_buf::<u16>(buf, self.len())?;
+
+        for s in self {
+            s.to_buf_with(buf)?;
+        }
+
+        Ok(())
     }
 
     fn size_hint() -> usize
@@ -31,7 +60,43 @@ where
     }
 
     fn approx_size(&self) -> usize {
-        u16::size_hint() + (S::size_hint() * self.len())
+        prefixed_approx_size::<Self, u16>(self)
+    }
+}
+
+impl<S> LengthPrefixed for Vec<S>
+where
+    S: SerBytes,
+{
+    fn items_len(&self) -> usize {
+        self.len()
+    }
+
+    fn items_from_buf<R>(buf: &mut R, len: usize) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        buf.claim_collection(len, size_of::<S>())?;
+
+        let mut v = Vec::with_capacity(preallocation::<S>(len));
+
+        for i in 0..len {
+            v.push(S::from_buf(buf).at_index(i)?);
+        }
+
+        Ok(v)
+    }
+
+    fn items_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        for s in self {
+            s.try_to_buf(buf)?;
+        }
+
+        Ok(())
+    }
+
+    fn items_approx_size(&self) -> usize {
+        S::size_hint() * self.len()
     }
 }
 
@@ -45,12 +110,15 @@ impl<L> SerBytes for U8Vec<L>
 where
     L: LengthLike,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         let mut inner = || {
-            let len = L::from_buf(buf)?.to_usize();
+            let len = length_from_buf::<L>(buf)?;
+            buf.claim_collection(len, 1)?;
+
             let bytes = buf.read_bytes(len)?;
 
             Ok(Self {
@@ -62,8 +130,41 @@ where
         inner().with_parent("U8Vec")
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        u8_slice_to_buf::<L>(buf, &self.vec);
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
+
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        u8_slice_to_buf::<L>(buf, &self.vec)
+    }
+}
+
+impl<'de> SerBytesBorrow<'de> for &'de [u8] {
+    /// Reads the same encoding as `U8Vec<u16>` and `Vec<u8>`, without copying the bytes
+    fn from_buf_borrowed(buf: &mut ReadByteBufferSlice<'de>) -> BBReadResult<Self> {
+        let mut inner = || {
+            let len = from_buf::<u16>(buf)? as usize;
+
+            buf.decode_budget()
+                .check_collection_len(len)
+                .map_err(|err| err.at(buf.checkpoint()))?;
+
+            buf.read_borrowed_bytes(len)
+        };
+
+        inner().with_parent("&[u8]")
+    }
+
+    fn to_buf_borrowed(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf_borrowed(buf));
+    }
+
+    fn try_to_buf_borrowed(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        u8_slice_to_buf::<u16>(buf, self)
+    }
+
+    fn approx_size_borrowed(&self) -> usize {
+        u16::size_hint() + self.len()
     }
 }
 
@@ -80,6 +181,12 @@ impl<L> U8Vec<L> {
     }
 }
 
+impl<L> Default for U8Vec<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<L> From<Vec<u8>> for U8Vec<L> {
     fn from(value: Vec<u8>) -> Self {
         Self::from_vec(value)
@@ -98,19 +205,22 @@ where
 {
     /// Reads bytes from a buffer into a [`VecDeque<S>`]
     ///
-    /// Uses a `u16` to determine the amount of bytes to read
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self> {
-        let mut inner = || vec_from_buf_u16(buf).map(|vec| vec.into());
+    /// Uses a `u16` to determine the amount of bytes to read, use [`LenPrefixed`](crate::prelude::LenPrefixed) for a different length type
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        let mut inner = || prefixed_from_buf::<Self, u16>(buf);
 
         inner().with_parent("VecDequeue")
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        buf.write_u16(self.len() as u16);
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
 
-        for el in self {
-            el.to_buf(buf);
-        }
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        prefixed_to_buf::<Self, u16>(buf, self)
     }
 
     fn size_hint() -> usize
@@ -121,6 +231,34 @@ where
     }
 
     fn approx_size(&self) -> usize {
-        u16::size_hint() + (S::size_hint() * self.len())
+        prefixed_approx_size::<Self, u16>(self)
+    }
+}
+
+impl<S> LengthPrefixed for VecDeque<S>
+where
+    S: SerBytes,
+{
+    fn items_len(&self) -> usize {
+        self.len()
+    }
+
+    fn items_from_buf<R>(buf: &mut R, len: usize) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        Vec::<S>::items_from_buf(buf, len).map(|vec| vec.into())
+    }
+
+    fn items_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        for el in self {
+            el.try_to_buf(buf)?;
+        }
+
+        Ok(())
+    }
+
+    fn items_approx_size(&self) -> usize {
+        S::size_hint() * self.len()
     }
 }
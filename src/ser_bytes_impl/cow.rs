@@ -1,23 +1,34 @@
-use crate::ser_trait::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WriteByteBufferOwned};
+use crate::ser_trait::{SerBytes, SerBytesBorrow};
+use bytebuffer::prelude::{
+    BBReadResult, BBWriteResult, ReadByteBuffer, ReadByteBufferSlice, WriteByteBuffer,
+};
 use std::borrow::Cow;
 
+/// Always deserializes to [`Cow::Owned`].
+///
+/// The `Cow<str>` and `Cow<[u8]>` fields of types deriving with `#[serbytes(borrow)]` are read with
+/// [`cow_from_buf_borrowed`] instead, which deserializes to [`Cow::Borrowed`]
 impl<'a, S, B> SerBytes for Cow<'a, B>
 where
     B: ToOwned<Owned = S> + ?Sized,
     S: SerBytes,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         Ok(Cow::Owned(S::from_buf(buf)?))
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
         self.clone().into_owned().to_buf(buf)
     }
 
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        self.clone().into_owned().try_to_buf(buf)
+    }
+
     fn size_hint() -> usize
     where
         Self: Sized,
@@ -32,3 +43,18 @@ where
         0
     }
 }
+
+/// Reads a `Cow` borrowing from the buffer, i.e. a `Cow<str>` or `Cow<[u8]>`
+///
+/// Every `Cow` of a [`SerBytes`] type implements [`SerBytesBorrow`] by value, so this is used by derived
+/// `#[serbytes(borrow)]` types to keep their `Cow` fields borrowed
+#[inline(always)]
+pub fn cow_from_buf_borrowed<'de, B>(
+    buf: &mut ReadByteBufferSlice<'de>,
+) -> BBReadResult<Cow<'de, B>>
+where
+    B: ToOwned + ?Sized,
+    &'de B: SerBytesBorrow<'de>,
+{
+    Ok(Cow::Borrowed(<&'de B>::from_buf_borrowed(buf)?))
+}
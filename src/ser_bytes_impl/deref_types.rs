@@ -1,7 +1,11 @@
 use crate::prelude::{SerBytes, from_buf};
-use crate::ser_bytes_impl::{into_slice_from_buf_u16, slice_to_buf_u16};
+use crate::ser_bytes_impl::{
+    LengthPrefixed, expect_written, prefixed_approx_size, prefixed_from_buf, prefixed_to_buf,
+};
 use crate::ser_trait::SerBytesStaticSized;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WriteByteBufferOwned};
+use bytebuffer::prelude::{
+    BBReadResult, BBWriteResult, ReadByteBuffer, WriteByteBuffer, WriteError,
+};
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::sync::Arc;
@@ -10,14 +14,21 @@ impl<S> SerBytes for Arc<S>
 where
     S: SerBytes,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self> {
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
         Ok(Self::new(from_buf(buf)?))
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
         S::to_buf(self, buf);
     }
 
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        S::try_to_buf(self, buf)
+    }
+
     fn size_hint() -> usize
     where
         Self: Sized,
@@ -26,7 +37,7 @@ where
     }
 
     fn approx_size(&self) -> usize {
-        S::approx_size(&self)
+        S::approx_size(self)
     }
 }
 
@@ -36,15 +47,59 @@ impl<S> SerBytes for Arc<[S]>
 where
     S: SerBytes,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+        Self: Sized,
+    {
+        prefixed_from_buf::<Self, u16>(buf)
+    }
+
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
+
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        prefixed_to_buf::<Self, u16>(buf, self)
+    }
+
+    fn size_hint() -> usize
     where
         Self: Sized,
     {
-        into_slice_from_buf_u16(buf)
+        u16::size_hint()
+    }
+
+    fn approx_size(&self) -> usize {
+        prefixed_approx_size::<Self, u16>(self)
+    }
+}
+
+impl<S> LengthPrefixed for Arc<[S]>
+where
+    S: SerBytes,
+{
+    fn items_len(&self) -> usize {
+        self.len()
+    }
+
+    fn items_from_buf<R>(buf: &mut R, len: usize) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        Vec::<S>::items_from_buf(buf, len).map(Self::from)
+    }
+
+    fn items_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        for s in self.iter() {
+            s.try_to_buf(buf)?;
+        }
+
+        Ok(())
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        slice_to_buf_u16(buf, &self)
+    fn items_approx_size(&self) -> usize {
+        S::size_hint() * self.len()
     }
 }
 
@@ -52,14 +107,21 @@ impl<S> SerBytes for Rc<S>
 where
     S: SerBytes,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self> {
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
         Ok(Self::new(S::from_buf(buf)?))
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
         S::to_buf(self, buf);
     }
 
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        S::try_to_buf(self, buf)
+    }
+
     fn size_hint() -> usize
     where
         Self: Sized,
@@ -68,7 +130,7 @@ where
     }
 
     fn approx_size(&self) -> usize {
-        S::approx_size(&self)
+        S::approx_size(self)
     }
 }
 
@@ -78,14 +140,25 @@ impl<S> SerBytes for RefCell<S>
 where
     S: SerBytes,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self> {
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
         Ok(Self::new(S::from_buf(buf)?))
     }
 
     /// Panics if the [RefCell] value is being mutable borrowed.
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
+
+    /// Errors if the [RefCell] value is being mutable borrowed.
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        let value = self
+            .try_borrow()
+            .map_err(|_| WriteError::invalid_state("RefCell is mutably borrowed"))?;
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        S::to_buf(&*self.borrow(), buf);
+        S::try_to_buf(&value, buf)
     }
 
     fn size_hint() -> usize
@@ -106,17 +179,22 @@ impl<S> SerBytes for Cell<S>
 where
     S: SerBytes + Copy,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         Ok(Cell::new(S::from_buf(buf)?))
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
         self.get().to_buf(buf);
     }
 
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        self.get().try_to_buf(buf)
+    }
+
     fn size_hint() -> usize
     where
         Self: Sized,
@@ -1,16 +1,32 @@
 use crate::ser_bytes_impl::from_buf;
 use crate::ser_trait::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WithParent, WriteByteBufferOwned};
+use bytebuffer::prelude::{
+    BBReadResult, ReadByteBuffer, ReadError, SpecificError, WithParent, WriteByteBuffer,
+};
 use std::time::Duration;
 
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
 impl SerBytes for Duration {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         let mut inner = || {
             let secs = from_buf(buf)?;
-            let nanos = from_buf(buf)?;
+            let start = buf.checkpoint();
+            let nanos: u32 = from_buf(buf)?;
+
+            // Only the nanoseconds within the last second are written, anything larger could overflow the seconds
+            if nanos >= NANOS_PER_SEC {
+                return Err(ReadError::new(
+                    SpecificError::Other("Subsecond nanoseconds out of range".into()),
+                    "Duration",
+                    None,
+                )
+                .at(start));
+            }
 
             Ok(Self::new(secs, nanos))
         };
@@ -18,7 +34,7 @@ impl SerBytes for Duration {
         inner().with_parent("Duration")
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
         self.as_secs().to_buf(buf);
         self.subsec_nanos().to_buf(buf);
     }
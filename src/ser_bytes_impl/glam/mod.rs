@@ -4,7 +4,7 @@ use crate::prelude::SerBytesStaticSized;
 use crate::ser_bytes_impl::from_buf;
 use crate::ser_bytes_impl::glam::vec_macro::{impl_for_vec2, impl_for_vec3, impl_for_vec4};
 use crate::ser_trait::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WriteByteBufferOwned};
+use bytebuffer::prelude::{BBReadResult, ReadByteBuffer, WriteByteBuffer};
 use glam::*;
 
 // Vec2
@@ -52,7 +52,10 @@ impl_for_vec4!(U64Vec4, u64);
 impl_for_vec4!(DVec4, f64);
 
 impl SerBytes for Vec4 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self> {
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
         Ok(Self::new(
             from_buf(buf)?,
             from_buf(buf)?,
@@ -61,7 +64,7 @@ impl SerBytes for Vec4 {
         ))
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
         self.x.to_buf(buf);
         self.y.to_buf(buf);
         self.z.to_buf(buf);
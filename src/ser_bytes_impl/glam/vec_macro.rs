@@ -1,7 +1,10 @@
 macro_rules! impl_for_vec {
     ($vec_ty:ty, $d_ty:ident, $dim:literal, $($field:ident),+) => {
         impl crate::prelude::SerBytes for $vec_ty {
-            fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self> {
+            fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+            where
+                R: ReadByteBuffer + ?Sized,
+            {
                 Ok(Self {
                     $(
                         $field: crate::prelude::from_buf(buf)?,
@@ -9,7 +12,7 @@ macro_rules! impl_for_vec {
                 })
             }
 
-            fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+            fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
                 $(
                     crate::prelude::to_buf(buf, &self.$field);
                 )+
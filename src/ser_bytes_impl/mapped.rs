@@ -1,5 +1,7 @@
 use crate::ser_trait::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WithParent, WriteByteBufferOwned};
+use bytebuffer::prelude::{
+    BBReadResult, BBWriteResult, ReadByteBuffer, WithParent, WriteByteBuffer,
+};
 use std::marker::PhantomData;
 
 #[derive(Debug)]
@@ -17,18 +19,31 @@ impl<S, M> Mapped<S, M> {
     }
 }
 
+/// Reads and writes an `S` in place of its own [`SerBytes`] impl, see [`Mapped`]
+///
+/// There are no size hooks, so mapped values don't count towards the `approx_size` of the types containing them
 pub trait MappedDataProvider<S> {
-    fn value_from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<S>;
+    fn value_from_buf<R>(buf: &mut R) -> BBReadResult<S>
+    where
+        R: ReadByteBuffer + ?Sized;
+
+    fn value_to_buf(value: &S, buf: &mut dyn WriteByteBuffer);
+
+    /// Writes the value, erroring if it can't be written. See [`SerBytes::try_to_buf`]
+    fn try_value_to_buf(value: &S, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        Self::value_to_buf(value, buf);
 
-    fn value_to_buf(value: &S, buf: &mut WriteByteBufferOwned);
+        Ok(())
+    }
 }
 
 impl<S, M> SerBytes for Mapped<S, M>
 where
     M: MappedDataProvider<S>,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         let mut inner_fn = || {
@@ -41,9 +56,13 @@ where
         inner_fn().with_parent("Mapped")
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
         M::value_to_buf(&self.inner, buf)
     }
+
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        M::try_value_to_buf(&self.inner, buf)
+    }
 }
 
 impl<S, M> Default for Mapped<S, M>
@@ -1,5 +1,7 @@
 use crate::ser_trait::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WriteByteBufferOwned};
+use bytebuffer::prelude::{
+    BBReadResult, BBWriteResult, ReadByteBuffer, WithParent, WriteByteBuffer,
+};
 use std::any::type_name;
 use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
@@ -10,6 +12,11 @@ pub trait MayNotExistDataProvider<T> {
     fn get_data() -> T;
 }
 
+/// A value which is read from the buffer if there is one, otherwise taken from `P`
+///
+/// Falls back to `P` only when the buffer has already ended where the value would start, so optional fields
+/// can safely be appended to the end of a format. A value that's started but fails to read, including one cut
+/// off by the end of the buffer, is an error, with the read head moved back to where the value started
 pub struct MayNotExistOrElse<S, P> {
     pub inner: S,
     _data_provider: PhantomData<P>,
@@ -20,16 +27,30 @@ where
     S: SerBytes,
     P: MayNotExistDataProvider<S>,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
-        let data = if let Ok(data) =
-            BBReadResult::from_buf(buf).expect("from_buf method on MayNotExist NEVER returns Err")
-        {
-            data
-        } else {
-            P::get_data()
+        if buf.at_end()? {
+            return Ok(Self {
+                inner: P::get_data(),
+                _data_provider: PhantomData,
+            });
+        }
+
+        let checkpoint = buf.checkpoint();
+        let budget = *buf.decode_budget();
+
+        let data = match S::from_buf(buf) {
+            Ok(data) => data,
+            Err(err) => {
+                // Nothing of a partially read value is kept, so whatever follows is read from the right place
+                buf.restore(checkpoint);
+                *buf.decode_budget_mut() = budget;
+
+                return Err(err).with_parent("MayNotExistOrElse");
+            }
         };
 
         Ok(Self {
@@ -38,10 +59,14 @@ where
         })
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
         self.inner.to_buf(buf);
     }
 
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        self.inner.try_to_buf(buf)
+    }
+
     /// Even though the type may not exist, we always write data to the buffer
     fn size_hint() -> usize
     where
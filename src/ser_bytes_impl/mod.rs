@@ -1,5 +1,6 @@
 pub mod atomic;
 pub mod box_impl;
+mod byte_bool;
 mod byte_tag;
 pub mod collections;
 pub mod cow;
@@ -11,25 +12,32 @@ mod json_like;
 mod mapped;
 mod may_not_exist;
 pub mod option;
+mod recoverable;
 pub mod result;
 mod ser_bytes_impl_macro;
 mod sized_block;
 mod skip_ser;
+mod var;
 mod versioning_wrapper;
 
+pub use byte_bool::*;
 pub use byte_tag::*;
 pub use collections::*;
+pub use cow::*;
 pub use json_like::*;
 pub use mapped::*;
 pub use may_not_exist::*;
+pub use recoverable::*;
 pub use sized_block::*;
 pub use skip_ser::*;
+pub use var::*;
 pub use versioning_wrapper::*;
 
 use crate::ser_bytes_impl::ser_bytes_impl_macro::{ser_data_impl, ser_data_impl_u};
-use crate::ser_trait::{SerBytes, SerBytesStaticSized};
+use crate::ser_trait::{SerBytes, SerBytesBorrow, SerBytesStaticSized};
 use bytebuffer::prelude::{
-    BBReadResult, ReadByteBufferRefMut, ReadError, SpecificError, WithParent, WriteByteBufferOwned,
+    BBReadResult, BBWriteResult, ReadByteBuffer, ReadByteBufferSlice, ReadError, SpecificError,
+    WithParent, WithPath, WriteByteBuffer, WriteError,
 };
 use std::cmp::Ordering;
 use std::marker::PhantomData;
@@ -54,72 +62,182 @@ ser_data_impl!(f64, f64, 8);
 pub trait LengthLike: SerBytes {
     fn from_usize(us: usize) -> Self;
 
+    /// Returns `None` if `us` is too large to be represented
+    fn try_from_usize(us: usize) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        Some(Self::from_usize(us))
+    }
+
     fn to_usize(self) -> usize;
+
+    /// Returns `None` if `self` is too large to be a `usize`
+    fn try_to_usize(self) -> Option<usize>
+    where
+        Self: Sized,
+    {
+        Some(self.to_usize())
+    }
+}
+
+/// The most bytes a collection allocates up front, so a length read from untrusted input can't
+/// allocate far more than the input actually holds
+const MAX_PREALLOCATION: usize = 64 * 1024;
+
+/// The capacity to allocate up front for a collection of `len` items of type `T`
+pub(crate) fn preallocation<T>(len: usize) -> usize {
+    len.min(MAX_PREALLOCATION / size_of::<T>().max(1))
 }
 
 #[inline(always)]
-pub fn from_buf<S>(buf: &mut ReadByteBufferRefMut) -> BBReadResult<S>
+pub fn from_buf<S>(buf: &mut (impl ReadByteBuffer + ?Sized)) -> BBReadResult<S>
 where
     S: SerBytes,
 {
     S::from_buf(buf)
 }
 
+/// Reads a type nested within another with `reader`, erroring if it's nested deeper than the reader's
+/// [`DecodeLimits`](bytebuffer::prelude::DecodeLimits) allow
+pub fn from_buf_nested<R, S, F>(buf: &mut R, reader: F) -> BBReadResult<S>
+where
+    R: ReadByteBuffer + ?Sized,
+    F: FnOnce(&mut R) -> BBReadResult<S>,
+{
+    buf.enter_nested()?;
+
+    let result = reader(buf);
+
+    buf.exit_nested();
+
+    result
+}
+
 #[inline(always)]
-pub fn to_buf<S>(buf: &mut WriteByteBufferOwned, s: &S)
+pub fn to_buf<S>(buf: &mut dyn WriteByteBuffer, s: &S)
 where
     S: SerBytes,
 {
     S::to_buf(s, buf)
 }
 
-pub fn slice_to_buf<S, L>(buf: &mut WriteByteBufferOwned, slice: &[S])
+#[inline(always)]
+pub fn try_to_buf<S>(buf: &mut dyn WriteByteBuffer, s: &S) -> BBWriteResult<()>
+where
+    S: SerBytes,
+{
+    S::try_to_buf(s, buf)
+}
+
+#[inline(always)]
+pub fn from_buf_borrowed<'de, S>(buf: &mut ReadByteBufferSlice<'de>) -> BBReadResult<S>
+where
+    S: SerBytesBorrow<'de>,
+{
+    S::from_buf_borrowed(buf)
+}
+
+#[inline(always)]
+pub fn to_buf_borrowed<'de, S>(buf: &mut dyn WriteByteBuffer, s: &S)
+where
+    S: SerBytesBorrow<'de>,
+{
+    S::to_buf_borrowed(s, buf)
+}
+
+#[inline(always)]
+pub fn try_to_buf_borrowed<'de, S>(buf: &mut dyn WriteByteBuffer, s: &S) -> BBWriteResult<()>
+where
+    S: SerBytesBorrow<'de>,
+{
+    S::try_to_buf_borrowed(s, buf)
+}
+
+/// Panics with the error if writing failed, used to implement [`SerBytes::to_buf`] through [`SerBytes::try_to_buf`]
+#[track_caller]
+pub fn expect_written(result: BBWriteResult<()>) {
+    if let Err(err) = result {
+        panic!("Failed to serialize: {err}");
+    }
+}
+
+/// Reads a length written as `L`, erroring rather than truncating if it doesn't fit into a `usize`
+pub fn length_from_buf<L>(buf: &mut (impl ReadByteBuffer + ?Sized)) -> BBReadResult<usize>
+where
+    L: LengthLike,
+{
+    let start = buf.checkpoint();
+
+    L::from_buf(buf)?.try_to_usize().ok_or_else(|| {
+        ReadError::new(SpecificError::LengthLimit, std::any::type_name::<L>(), None).at(start)
+    })
+}
+
+/// Writes `len` as `L`, erroring rather than truncating if it doesn't fit
+pub fn length_to_buf<L>(buf: &mut dyn WriteByteBuffer, len: usize) -> BBWriteResult<()>
+where
+    L: LengthLike,
+{
+    let length = L::try_from_usize(len).ok_or(WriteError::LengthOverflow {
+        len,
+        length_type: std::any::type_name::<L>(),
+    })?;
+
+    length.try_to_buf(buf)
+}
+
+pub fn slice_to_buf<S, L>(buf: &mut dyn WriteByteBuffer, slice: &[S]) -> BBWriteResult<()>
 where
     S: SerBytes,
     L: LengthLike,
 {
     buf.reserve(S::size_hint() * slice.len());
 
-    L::from_usize(slice.len()).to_buf(buf);
+    length_to_buf::<L>(buf, slice.len())?;
 
     for s in slice {
-        s.to_buf(buf);
+        s.try_to_buf(buf)?;
     }
+
+    Ok(())
 }
 
 #[inline(always)]
-pub fn slice_to_buf_u16<S>(buf: &mut WriteByteBufferOwned, slice: &[S])
+pub fn slice_to_buf_u16<S>(buf: &mut dyn WriteByteBuffer, slice: &[S]) -> BBWriteResult<()>
 where
     S: SerBytes,
 {
     slice_to_buf::<S, u16>(buf, slice)
 }
 
-pub fn vec_from_buf<S, L>(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Vec<S>>
+pub fn vec_from_buf<S, L>(buf: &mut (impl ReadByteBuffer + ?Sized)) -> BBReadResult<Vec<S>>
 where
     S: SerBytes,
     L: LengthLike,
 {
-    let len = L::from_buf(buf)?.to_usize();
+    let len = length_from_buf::<L>(buf)?;
+
+    buf.claim_collection(len, size_of::<S>())?;
 
-    let mut v = Vec::with_capacity(len);
+    let mut v = Vec::with_capacity(preallocation::<S>(len));
 
-    for _ in 0..len {
-        v.push(S::from_buf(buf)?);
+    for i in 0..len {
+        v.push(S::from_buf(buf).at_index(i)?);
     }
 
     Ok(v)
 }
 
 #[inline(always)]
-pub fn vec_from_buf_u16<S>(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Vec<S>>
+pub fn vec_from_buf_u16<S>(buf: &mut (impl ReadByteBuffer + ?Sized)) -> BBReadResult<Vec<S>>
 where
     S: SerBytes,
 {
     vec_from_buf::<S, u16>(buf)
 }
 
-pub fn into_slice_from_buf<S, L, O>(buf: &mut ReadByteBufferRefMut) -> BBReadResult<O>
+pub fn into_slice_from_buf<S, L, O>(buf: &mut (impl ReadByteBuffer + ?Sized)) -> BBReadResult<O>
 where
     S: SerBytes,
     L: LengthLike,
@@ -130,7 +248,7 @@ where
     Ok(v.into())
 }
 
-pub fn into_slice_from_buf_u16<S, O>(buf: &mut ReadByteBufferRefMut) -> BBReadResult<O>
+pub fn into_slice_from_buf_u16<S, O>(buf: &mut (impl ReadByteBuffer + ?Sized)) -> BBReadResult<O>
 where
     S: SerBytes,
     O: From<Vec<S>>,
@@ -138,46 +256,52 @@ where
     into_slice_from_buf::<S, u16, O>(buf)
 }
 
-pub fn u8_slice_to_buf<L>(buf: &mut WriteByteBufferOwned, slice: &[u8])
+pub fn u8_slice_to_buf<L>(buf: &mut dyn WriteByteBuffer, slice: &[u8]) -> BBWriteResult<()>
 where
     L: LengthLike,
 {
-    L::from_usize(slice.len()).to_buf(buf);
+    length_to_buf::<L>(buf, slice.len())?;
 
     buf.write_bytes(slice);
+
+    Ok(())
 }
 
 impl SerBytes for () {
-    fn from_buf(_: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(_: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         Ok(())
     }
 
-    fn to_buf(&self, _: &mut WriteByteBufferOwned) {}
+    fn to_buf(&self, _: &mut dyn WriteByteBuffer) {}
 }
 
 impl SerBytesStaticSized for () {}
 
 impl<T> SerBytes for PhantomData<T> {
-    fn from_buf(_: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(_: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         Ok(PhantomData)
     }
 
-    fn to_buf(&self, _: &mut WriteByteBufferOwned) {}
+    fn to_buf(&self, _: &mut dyn WriteByteBuffer) {}
 }
 
 impl<T> SerBytesStaticSized for PhantomData<T> {}
 
 impl SerBytes for Ordering {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
+        let start = buf.checkpoint();
         let ord_int = u8::from_buf(buf).with_parent("Ordering")?;
 
         let ord = match ord_int {
@@ -189,17 +313,21 @@ impl SerBytes for Ordering {
 
             _ => {
                 return Err(ReadError::new(
-                    SpecificError::Other("Enum index out of bounds".into()),
+                    SpecificError::EnumOrdinalOutOfBounds {
+                        max_bound: 2,
+                        got: ord_int.into(),
+                    },
                     "Ordering",
                     None,
-                ));
+                )
+                .at(start));
             }
         };
 
         Ok(ord)
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
         let ord_int = match self {
             Self::Less => 0,
             Self::Equal => 1,
@@ -1,24 +1,35 @@
 use crate::mapped::SerbytesMapped;
 use crate::ser_trait::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WriteByteBufferOwned};
+use bytebuffer::prelude::{BBReadResult, BBWriteResult, ReadByteBuffer, WriteByteBuffer};
 
 impl<S> SerBytes for Option<S>
 where
     S: SerBytes,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         Self::from_buf_mapped(buf, |buf| S::from_buf(buf))
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
         self.to_buf_mapped(buf, |buf, value| {
             value.to_buf(buf);
         })
     }
 
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        self.is_some().to_buf(buf);
+
+        if let Some(value) = self {
+            value.try_to_buf(buf)?;
+        }
+
+        Ok(())
+    }
+
     fn size_hint() -> usize {
         bool::size_hint()
     }
@@ -29,10 +40,11 @@ where
 }
 
 impl<T> SerbytesMapped<T> for Option<T> {
-    fn from_buf_mapped<F>(buf: &mut ReadByteBufferRefMut, reader: F) -> BBReadResult<Self>
+    fn from_buf_mapped<R, F>(buf: &mut R, reader: F) -> BBReadResult<Self>
     where
         Self: Sized,
-        F: FnOnce(&mut ReadByteBufferRefMut) -> BBReadResult<T>,
+        R: ReadByteBuffer + ?Sized,
+        F: FnOnce(&mut R) -> BBReadResult<T>,
     {
         if buf.read_bool()? {
             Ok(Some(reader(buf)?))
@@ -41,9 +53,9 @@ impl<T> SerbytesMapped<T> for Option<T> {
         }
     }
 
-    fn to_buf_mapped<F>(&self, buf: &mut WriteByteBufferOwned, writer: F)
+    fn to_buf_mapped<F>(&self, buf: &mut dyn WriteByteBuffer, writer: F)
     where
-        F: FnOnce(&mut WriteByteBufferOwned, &T),
+        F: FnOnce(&mut dyn WriteByteBuffer, &T),
     {
         if let Some(value) = self {
             true.to_buf(buf);
@@ -0,0 +1,161 @@
+use crate::ser_bytes_impl::sized_block::block_from_buf;
+use crate::ser_bytes_impl::{
+    LenPrefixed, LengthLike, SizedBlock, expect_written, length_from_buf, preallocation,
+};
+use crate::ser_trait::SerBytes;
+use bytebuffer::prelude::{
+    BBReadResult, BBWriteResult, ReadByteBuffer, ReadError, WithParent, WithPath, WriteByteBuffer,
+};
+
+/// A type which can be read leniently, skipping the parts of it that fail to decode
+pub trait RecoverFromBuf: Sized {
+    /// Reads the type, pushing the error of every part that was skipped onto `errors`
+    ///
+    /// Only errors that can't be skipped past are returned, i.e. the buffer ending early
+    fn recover_from_buf<R>(buf: &mut R, errors: &mut Vec<ReadError>) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized;
+}
+
+/// Decodes `C` leniently, keeping everything that decoded and the errors of everything that didn't
+///
+/// i.e. a `Recoverable<Vec<SizedBlock<T>>>` skips the blocks that fail to decode, so a partially corrupted
+/// file still loads. Written exactly the same as `C`, so it can be swapped in without changing the format
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recoverable<C> {
+    pub inner: C,
+    /// The errors of the skipped parts, in the order they were read
+    pub errors: Vec<ReadError>,
+}
+
+impl<C> Recoverable<C> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Whether nothing was skipped while decoding
+    pub fn is_intact(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C> SerBytes for Recoverable<C>
+where
+    C: RecoverFromBuf + SerBytes,
+{
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+        Self: Sized,
+    {
+        let mut errors = Vec::new();
+        let inner = C::recover_from_buf(buf, &mut errors).with_parent("Recoverable")?;
+
+        Ok(Self { inner, errors })
+    }
+
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
+
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        self.inner.try_to_buf(buf)
+    }
+
+    fn size_hint() -> usize
+    where
+        Self: Sized,
+    {
+        C::size_hint()
+    }
+
+    fn approx_size(&self) -> usize {
+        self.inner.approx_size()
+    }
+}
+
+impl<C> Default for Recoverable<C>
+where
+    C: Default,
+{
+    fn default() -> Self {
+        Self::new(C::default())
+    }
+}
+
+impl<C> From<C> for Recoverable<C> {
+    fn from(value: C) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Reads `len` blocks, skipping the ones whose contents fail to decode
+fn blocks_from_buf<S, L, R>(
+    buf: &mut R,
+    len: usize,
+    errors: &mut Vec<ReadError>,
+) -> BBReadResult<Vec<SizedBlock<S, L>>>
+where
+    S: SerBytes,
+    L: LengthLike,
+    R: ReadByteBuffer + ?Sized,
+{
+    buf.claim_collection(len, size_of::<SizedBlock<S, L>>())?;
+
+    let mut blocks = Vec::with_capacity(preallocation::<SizedBlock<S, L>>(len));
+
+    for i in 0..len {
+        match block_from_buf::<S, L, R>(buf).at_index(i)? {
+            Ok(inner) => blocks.push(SizedBlock::new_with_len_type(inner)),
+            Err(err) => errors.push(err.new_parent("SizedBlock").at_index(i)),
+        }
+    }
+
+    Ok(blocks)
+}
+
+impl<S, L> RecoverFromBuf for Vec<SizedBlock<S, L>>
+where
+    S: SerBytes,
+    L: LengthLike,
+{
+    fn recover_from_buf<R>(buf: &mut R, errors: &mut Vec<ReadError>) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        let mut inner = || {
+            let len = length_from_buf::<u16>(buf)?;
+
+            blocks_from_buf(buf, len, errors)
+        };
+
+        inner().with_parent("Vec")
+    }
+}
+
+impl<S, L, LL> RecoverFromBuf for LenPrefixed<Vec<SizedBlock<S, L>>, LL>
+where
+    S: SerBytes,
+    L: LengthLike,
+    LL: LengthLike,
+{
+    fn recover_from_buf<R>(buf: &mut R, errors: &mut Vec<ReadError>) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+    {
+        let mut inner = || {
+            let len = length_from_buf::<LL>(buf)?;
+
+            blocks_from_buf(buf, len, errors).map(Self::new)
+        };
+
+        inner().with_parent("LenPrefixed")
+    }
+}
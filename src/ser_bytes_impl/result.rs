@@ -1,27 +1,35 @@
 use crate::prelude::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, ReadError, WriteByteBufferOwned};
+use crate::ser_bytes_impl::expect_written;
+use bytebuffer::prelude::{
+    BBReadResult, BBWriteResult, ReadByteBuffer, ReadError, WriteByteBuffer, WriteError,
+};
 
-impl<'a, S, E> SerBytes for Result<S, E>
+impl<S, E> SerBytes for Result<S, E>
 where
     S: SerBytes,
-    E: From<ReadError<'a>>,
+    E: From<ReadError>,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         Ok(S::from_buf(buf).map_err(|e| e.into()))
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    /// Panics on the `Err` variant
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
+
+    /// Errors on the `Err` variant, as only the `Ok` variant is ever written
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
         match &self {
-            Ok(s) => {
-                s.to_buf(buf);
-            }
+            Ok(s) => s.try_to_buf(buf),
 
-            Err(_) => {
-                panic!("Attempt to write error variant to buffer")
-            }
+            Err(_) => Err(WriteError::invalid_state(
+                "Attempt to write error variant to buffer",
+            )),
         }
     }
 
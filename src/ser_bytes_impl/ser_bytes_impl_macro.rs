@@ -2,21 +2,34 @@ macro_rules! ser_data_impl {
     ($t:ty, $call_signature:ident, $byte_size:literal) => {
         impl crate::ser_trait::SerBytes for $t {
             #[inline(always)]
-            fn from_buf(
-                buf: &mut bytebuffer::prelude::ReadByteBufferRefMut,
-            ) -> bytebuffer::prelude::BBReadResult<Self> {
+            fn from_buf<R>(buf: &mut R) -> bytebuffer::prelude::BBReadResult<Self>
+            where
+                R: ReadByteBuffer + ?Sized,
+            {
                 paste::paste! {
                     buf.[<read_ $call_signature>]()
                 }
             }
 
             #[inline(always)]
-            fn to_buf(&self, buf: &mut bytebuffer::prelude::WriteByteBufferOwned) {
+            fn to_buf(&self, buf: &mut dyn bytebuffer::prelude::WriteByteBuffer) {
                 paste::paste! {
                     buf.[<write_ $call_signature>](*self);
                 }
             }
 
+            #[inline(always)]
+            fn to_buf_with<W>(&self, buf: &mut W) -> bytebuffer::prelude::BBWriteResult<()>
+            where
+                W: bytebuffer::prelude::WriteByteBuffer,
+            {
+                paste::paste! {
+                    buf.[<write_ $call_signature>](*self);
+                }
+
+                Ok(())
+            }
+
             #[doc = concat!("Size hint for a ", stringify!($t))]
             #[doc = ""]
             #[doc = concat!("This data takes up ", stringify!($byte_size), " byte(s) and as such this function will always return ", stringify!($byte_size))]
@@ -48,10 +61,20 @@ macro_rules! ser_data_impl_u {
                 us as Self
             }
 
+            #[inline(always)]
+            fn try_from_usize(us: usize) -> Option<Self> {
+                Self::try_from(us).ok()
+            }
+
             #[inline(always)]
             fn to_usize(self) -> usize {
                 self as usize
             }
+
+            #[inline(always)]
+            fn try_to_usize(self) -> Option<usize> {
+                usize::try_from(self).ok()
+            }
         }
     };
 }
@@ -1,8 +1,8 @@
-use crate::bytebuffer::index_pointer::write::IndexPointerWrite;
-use crate::ser_bytes_impl::{LengthLike, from_buf};
-use crate::ser_trait::{SerBytes, SerBytesStaticSized};
+use crate::ser_bytes_impl::{LengthLike, expect_written, from_buf, length_from_buf, length_to_buf};
+use crate::ser_trait::SerBytes;
 use bytebuffer::prelude::{
-    BBReadResult, ReadByteBufferRefMut, ReadByteBufferSlice, WithParent, WriteByteBufferOwned,
+    BBReadResult, BBWriteResult, Packing, ReadByteBuffer, ReadByteBufferSlice, WithParent,
+    WriteByteBuffer, WriteByteBufferOwned,
 };
 use std::marker::PhantomData;
 
@@ -39,20 +39,18 @@ impl<S, L> SizedBlock<S, L> {
 impl<S, L> SerBytes for SizedBlock<S, L>
 where
     S: SerBytes,
-    L: SerBytes + LengthLike + SerBytesStaticSized,
+    L: LengthLike,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         let mut inner_fn = || {
-            let len = L::from_buf(buf)?.to_usize();
-            let bytes = buf.read_bytes(len)?;
-
-            let mut block_buffer = ReadByteBufferSlice::new(bytes);
+            let inner = block_from_buf::<S, L, R>(buf)??;
 
             Ok(Self {
-                inner: from_buf(&mut block_buffer.rbb_ref_mut())?,
+                inner,
                 _len: PhantomData,
             })
         };
@@ -60,14 +58,14 @@ where
         inner_fn().with_parent("SizedBlock")
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        let len_index_ptr = buf.write_with_index_pointer(&L::from_usize(0));
-
-        let data_index_ptr = buf.write_with_index_pointer(&self.inner);
-
-        let data_len = data_index_ptr.len();
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
 
-        buf.write_at_index_pointer(len_index_ptr, &L::from_usize(data_len));
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        block_to_buf_with::<L, _>(buf, self.inner.approx_size(), |block| {
+            self.inner.try_to_buf(block)
+        })
     }
 
     fn size_hint() -> usize
@@ -82,6 +80,67 @@ where
     }
 }
 
+/// Reads a block's contents, the outer error is from reading the block itself and the inner error from
+/// reading its contents. The read head is after the block whenever the outer result is `Ok`
+pub(crate) fn block_from_buf<S, L, R>(buf: &mut R) -> BBReadResult<BBReadResult<S>>
+where
+    S: SerBytes,
+    L: LengthLike,
+    R: ReadByteBuffer + ?Sized,
+{
+    block_from_buf_with::<L, _, _, _>(buf, |block| from_buf(block))
+}
+
+/// Reads a block written by [`block_to_buf_with`], reading its contents with `reader`
+///
+/// The outer error is from reading the block itself and the inner error from reading its contents. The
+/// read head is after the block whenever the outer result is `Ok`, even if `reader` didn't read all of it
+pub fn block_from_buf_with<L, R, T, F>(buf: &mut R, reader: F) -> BBReadResult<BBReadResult<T>>
+where
+    L: LengthLike,
+    R: ReadByteBuffer + ?Sized,
+    F: FnOnce(&mut ReadByteBufferSlice<'_>) -> BBReadResult<T>,
+{
+    let len = length_from_buf::<L>(buf)?;
+    let checkpoint = buf.checkpoint();
+    // Aligned reads skip the rest of a partially read byte before the block
+    let skips_partial = checkpoint.bit_position() != 0 && buf.packing() == Packing::Aligned;
+    let block_start = checkpoint.position() + skips_partial as usize;
+    let mut block_buffer = buf.sub_reader(len)?;
+
+    // Positions within the block are relative to its start
+    let inner = reader(&mut block_buffer).map_err(|err| err.offset_position(block_start));
+
+    // What the block used of the decode limits counts towards the whole buffer
+    let budget = *block_buffer.decode_budget();
+    *buf.decode_budget_mut() = budget;
+
+    Ok(inner)
+}
+
+/// Writes whatever `writer` writes as a block, prefixed by its length as an `L`
+pub fn block_to_buf_with<L, F>(
+    buf: &mut dyn WriteByteBuffer,
+    approx_size: usize,
+    writer: F,
+) -> BBWriteResult<()>
+where
+    L: LengthLike,
+    F: FnOnce(&mut dyn WriteByteBuffer) -> BBWriteResult<()>,
+{
+    // The length has to be known before the block is written, so the block is written to a separate buffer first
+    let mut block_buffer = WriteByteBufferOwned::with_capacity(approx_size)
+        .with_endianness(buf.endianness())
+        .with_packing(buf.packing());
+
+    writer(&mut block_buffer)?;
+
+    length_to_buf::<L>(buf, block_buffer.len())?;
+    buf.write_bytes(block_buffer.buf());
+
+    Ok(())
+}
+
 impl<S, L> Default for SizedBlock<S, L>
 where
     S: Default,
@@ -1,5 +1,5 @@
 use crate::ser_trait::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WriteByteBufferOwned};
+use bytebuffer::prelude::{BBReadResult, ReadByteBuffer, WriteByteBuffer};
 use std::marker::PhantomData;
 
 pub trait FallbackDataProvider<S> {
@@ -15,8 +15,9 @@ impl<S, F> SerBytes for SkipSerialization<S, F>
 where
     F: FallbackDataProvider<S>,
 {
-    fn from_buf(_: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(_: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         Ok(Self {
@@ -25,7 +26,7 @@ where
         })
     }
 
-    fn to_buf(&self, _: &mut WriteByteBufferOwned) {}
+    fn to_buf(&self, _: &mut dyn WriteByteBuffer) {}
 }
 
 impl<S, F> SkipSerialization<S, F> {
@@ -0,0 +1,114 @@
+use crate::ser_bytes_impl::LengthLike;
+use crate::ser_trait::SerBytes;
+use bytebuffer::prelude::{BBReadResult, BBWriteResult, ReadByteBuffer, WriteByteBuffer};
+
+/// A variable length integer, which takes up fewer bytes the closer the value is to 0
+///
+/// Unsigned integers are LEB128 encoded, signed integers are ZigZag encoded beforehand
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Var<T>(pub T);
+
+impl<T> From<T> for Var<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+/// The number of bytes a LEB128 encoded value with `bits` significant bits takes up
+#[inline(always)]
+fn varint_len(bits: u32) -> usize {
+    bits.max(1).div_ceil(7) as usize
+}
+
+macro_rules! var_impl {
+    ($t:ty, $read:ident, $write:ident, $bits:expr) => {
+        impl SerBytes for Var<$t> {
+            #[inline(always)]
+            fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+            where
+                R: ReadByteBuffer + ?Sized,
+            {
+                buf.$read().map(Self)
+            }
+
+            #[inline(always)]
+            fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+                buf.$write(self.0);
+            }
+
+            #[inline(always)]
+            fn to_buf_with<W>(&self, buf: &mut W) -> BBWriteResult<()>
+            where
+                W: WriteByteBuffer,
+            {
+                buf.$write(self.0);
+
+                Ok(())
+            }
+
+            /// A varint always takes up at least a byte
+            #[inline(always)]
+            fn size_hint() -> usize
+            where
+                Self: Sized,
+            {
+                1
+            }
+
+            #[inline(always)]
+            fn approx_size(&self) -> usize {
+                let bits: fn($t) -> u32 = $bits;
+
+                varint_len(bits(self.0))
+            }
+        }
+    };
+}
+
+macro_rules! var_impl_u {
+    ($t:ty, $read:ident, $write:ident) => {
+        var_impl!($t, $read, $write, |n| <$t>::BITS - n.leading_zeros());
+
+        impl LengthLike for Var<$t> {
+            #[inline(always)]
+            fn from_usize(us: usize) -> Self {
+                Self(us as $t)
+            }
+
+            #[inline(always)]
+            fn try_from_usize(us: usize) -> Option<Self> {
+                <$t>::try_from(us).ok().map(Self)
+            }
+
+            #[inline(always)]
+            fn to_usize(self) -> usize {
+                self.0 as usize
+            }
+
+            #[inline(always)]
+            fn try_to_usize(self) -> Option<usize> {
+                usize::try_from(self.0).ok()
+            }
+        }
+    };
+}
+
+macro_rules! var_impl_i {
+    ($t:ty, $read:ident, $write:ident) => {
+        var_impl!($t, $read, $write, |n| {
+            let zigzag = (n << 1) ^ (n >> (<$t>::BITS - 1));
+
+            <$t>::BITS - zigzag.leading_zeros()
+        });
+    };
+}
+
+var_impl_u!(u16, read_varint_u16, write_varint_u16);
+var_impl_u!(u32, read_varint_u32, write_varint_u32);
+var_impl_u!(u64, read_varint_u64, write_varint_u64);
+var_impl_u!(u128, read_varint_u128, write_varint_u128);
+
+var_impl_i!(i16, read_varint_i16, write_varint_i16);
+var_impl_i!(i32, read_varint_i32, write_varint_i32);
+var_impl_i!(i64, read_varint_i64, write_varint_i64);
+var_impl_i!(i128, read_varint_i128, write_varint_i128);
@@ -1,9 +1,14 @@
+use crate::ser_bytes_impl::{Var, expect_written};
 use crate::ser_trait::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WithParent, WriteByteBufferOwned};
+use bytebuffer::prelude::{
+    BBReadResult, BBWriteResult, ReadByteBuffer, WithParent, WriteByteBuffer,
+};
 
 pub trait CurrentVersion: SerBytes {
     type Output;
-    fn get_data_from_buf(&self, buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self::Output>;
+    fn get_data_from_buf<R>(&self, buf: &mut R) -> BBReadResult<Self::Output>
+    where
+        R: ReadByteBuffer + ?Sized;
 
     fn current_version() -> Self;
 }
@@ -19,8 +24,9 @@ where
     D: SerBytes,
     V: CurrentVersion<Output = D>,
 {
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized,
     {
         let mut inner = || {
@@ -36,11 +42,15 @@ where
         inner().with_parent("VersioningWrapper")
     }
 
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
+
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
         buf.reserve(self.version_read.approx_size() + self.inner.approx_size());
 
-        V::current_version().to_buf(buf);
-        self.inner.to_buf(buf);
+        V::current_version().try_to_buf(buf)?;
+        self.inner.try_to_buf(buf)
     }
 
     fn size_hint() -> usize
@@ -106,3 +116,120 @@ where
         self.version_read != V::current_version()
     }
 }
+
+/// A type whose fields depend on the version of its format, derived by `#[serbytes(version = N)]`
+///
+/// [`SerBytes`] reads and writes the current version. Reading an older version needs the version the
+/// data was written with, i.e. from the header of a [`Versioned`] or the [`CurrentVersion`] of a [`VersioningWrapper`]
+pub trait SerBytesVersioned: SerBytes {
+    /// The version written by [`SerBytes::to_buf`]
+    const CURRENT_VERSION: u32;
+
+    /// Reads data written by `version`, erroring if it's newer than [`SerBytesVersioned::CURRENT_VERSION`]
+    fn from_buf_versioned<R>(buf: &mut R, version: u32) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+        Self: Sized;
+}
+
+/// Writes the current version of `D` as a `Var<u32>` before it, so data written by older versions can still be read
+#[derive(Debug, Copy, Clone)]
+pub struct Versioned<D> {
+    pub inner: D,
+    version_read: u32,
+}
+
+impl<D> SerBytes for Versioned<D>
+where
+    D: SerBytesVersioned,
+{
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+        Self: Sized,
+    {
+        let mut inner = || {
+            let Var(version) = Var::<u32>::from_buf(buf)?;
+            let data = D::from_buf_versioned(buf, version)?;
+
+            Ok(Self {
+                inner: data,
+                version_read: version,
+            })
+        };
+
+        inner().with_parent("Versioned")
+    }
+
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        expect_written(self.try_to_buf(buf));
+    }
+
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        buf.reserve(self.approx_size());
+
+        Var(D::CURRENT_VERSION).try_to_buf(buf)?;
+        self.inner.try_to_buf(buf)
+    }
+
+    fn size_hint() -> usize
+    where
+        Self: Sized,
+    {
+        Var::<u32>::size_hint() + D::size_hint()
+    }
+
+    fn approx_size(&self) -> usize {
+        Var(D::CURRENT_VERSION).approx_size() + self.inner.approx_size()
+    }
+}
+
+impl<D> Default for Versioned<D>
+where
+    D: SerBytesVersioned + Default,
+{
+    fn default() -> Self {
+        Self::new(D::default())
+    }
+}
+
+impl<D> PartialEq for Versioned<D>
+where
+    D: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<D> Eq for Versioned<D> where D: Eq {}
+
+impl<D> From<D> for Versioned<D>
+where
+    D: SerBytesVersioned,
+{
+    fn from(value: D) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<D> Versioned<D>
+where
+    D: SerBytesVersioned,
+{
+    pub fn new(data: D) -> Self {
+        Self {
+            inner: data,
+            version_read: D::CURRENT_VERSION,
+        }
+    }
+
+    /// The version the data was written with, the current version if it wasn't read
+    pub fn version_read(&self) -> u32 {
+        self.version_read
+    }
+
+    pub fn did_update(&self) -> bool {
+        self.version_read != D::CURRENT_VERSION
+    }
+}
@@ -1,18 +1,46 @@
 use bytebuffer::prelude::{
-    BBReadResult, ReadByteBufferOwned, ReadByteBufferRefMut, WriteByteBufferOwned,
+    BBReadResult, BBWriteResult, ReadByteBuffer, ReadByteBufferOwned, ReadByteBufferSlice,
+    ReadByteBufferStream, WriteByteBuffer, WriteByteBufferOwned, WriteByteBufferSlice,
+    WriteByteBufferStream,
 };
+use std::io;
 
 /// [`SerBytes`]
 ///
 /// A type that can be both serialized and deserialized from a binary source.
 pub trait SerBytes {
-    /// Reads and deserializes the type from the provided [`ReadByteBufferRefMut`]
-    fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    /// Reads and deserializes the type from the provided reader
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
     where
+        R: ReadByteBuffer + ?Sized,
         Self: Sized;
 
-    /// Serializes and writes the type to the provided [`WriteByteBufferOwned`]
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned);
+    /// Serializes and writes the type to the provided writer
+    ///
+    /// Takes a trait object rather than a generic so that [`SerBytes`] stays usable as `dyn SerBytes`
+    ///
+    /// Types which can fail to serialize should implement [`SerBytes::try_to_buf`] and panic with its error here
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer);
+
+    /// Serializes and writes the type to the provided writer, erroring if the type can't be serialized
+    ///
+    /// Types containing other types should implement this so that errors from their contents are propagated
+    fn try_to_buf(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        self.to_buf(buf);
+
+        Ok(())
+    }
+
+    /// Same as [`SerBytes::try_to_buf`], for a writer whose type is known so primitives are written without a dynamic call
+    ///
+    /// Defaults to [`SerBytes::try_to_buf`], primitives and [`Vec`]s of them write to `buf` directly
+    fn to_buf_with<W>(&self, buf: &mut W) -> BBWriteResult<()>
+    where
+        W: WriteByteBuffer,
+        Self: Sized,
+    {
+        self.try_to_buf(buf)
+    }
 
     fn from_vec(vec: Vec<u8>) -> BBReadResult<Self>
     where
@@ -20,25 +48,85 @@ pub trait SerBytes {
     {
         let mut buf = ReadByteBufferOwned::from_vec(vec);
 
-        Self::from_buf(&mut buf.rbb_ref_mut())
+        Self::from_buf(&mut buf)
     }
 
     fn from_bytes(bytes: &[u8]) -> BBReadResult<Self>
     where
         Self: Sized,
     {
-        let mut index = 0;
-        let mut bit_index = 0;
+        let mut buf = ReadByteBufferSlice::new(bytes);
+
+        Self::from_buf(&mut buf)
+    }
+
+    /// Like [`SerBytes::from_vec`], but errors with [`SpecificError::TrailingBytes`](bytebuffer::prelude::SpecificError::TrailingBytes) if any bytes are left over
+    fn from_vec_exact(vec: Vec<u8>) -> BBReadResult<Self>
+    where
+        Self: Sized,
+    {
+        let mut buf = ReadByteBufferOwned::from_vec(vec);
 
-        let mut rbb = ReadByteBufferRefMut::from_bytes(bytes, &mut index, &mut bit_index);
+        let value = Self::from_buf(&mut buf)?;
+        buf.ensure_consumed()?;
 
-        Self::from_buf(&mut rbb)
+        Ok(value)
     }
 
-    fn to_bb(&self) -> WriteByteBufferOwned {
+    /// Like [`SerBytes::from_bytes`], but errors with [`SpecificError::TrailingBytes`](bytebuffer::prelude::SpecificError::TrailingBytes) if any bytes are left over
+    fn from_bytes_exact(bytes: &[u8]) -> BBReadResult<Self>
+    where
+        Self: Sized,
+    {
+        let mut buf = ReadByteBufferSlice::new(bytes);
+
+        let value = Self::from_buf(&mut buf)?;
+        buf.ensure_consumed()?;
+
+        Ok(value)
+    }
+
+    /// Reads and deserializes a single value from an [`io::Read`] source.
+    ///
+    /// Only the bytes making up the value are consumed from the source, so consecutive values can be
+    /// read from the same source by calling this repeatedly.
+    fn from_reader<R>(reader: R) -> BBReadResult<Self>
+    where
+        R: io::Read,
+        Self: Sized,
+    {
+        let mut buf = ReadByteBufferStream::new(reader);
+
+        Self::from_buf(&mut buf)
+    }
+
+    fn to_bb(&self) -> BBWriteResult<WriteByteBufferOwned> {
         let mut buf = WriteByteBufferOwned::with_capacity(self.approx_size());
-        self.to_buf(&mut buf);
-        buf
+        self.try_to_buf(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Serializes and writes the type to an [`io::Write`] sink, buffering at most a small, fixed amount of data in memory
+    fn to_writer<W>(&self, writer: W) -> BBWriteResult<()>
+    where
+        W: io::Write,
+        Self: Sized,
+    {
+        let mut buf = WriteByteBufferStream::new(writer);
+        self.to_buf_with(&mut buf)?;
+        buf.finish()?;
+
+        Ok(())
+    }
+
+    /// Serializes and writes the type into `slice` without allocating, returning the number of bytes written
+    ///
+    /// Errors with [`WriteError::SliceOverflow`](bytebuffer::prelude::WriteError::SliceOverflow) if the serialized
+    /// data doesn't fit into the slice, in which case the contents of the slice are unspecified
+    fn to_slice(&self, slice: &mut [u8]) -> BBWriteResult<usize> {
+        let mut buf = WriteByteBufferSlice::new(slice);
+        self.try_to_buf(&mut buf)?;
+        Ok(buf.finish()?)
     }
 
     /// The absolute minimum amount of data that the serialized data will take up in bytes
@@ -53,16 +141,83 @@ pub trait SerBytes {
     /// The approximate size of a type at runtime. Statically sized types when serialized (i.e. primitives) should just call Self::size_hint from this function.
     ///
     /// Types that can have varying sizes of serialized data (enums, hashmaps, etc.) should do their best to approximate the size as cheaply as possible
-
     fn approx_size(&self) -> usize {
         0
     }
 }
 
+/// [`SerBytesBorrow`]
+///
+/// A type that can be deserialized by borrowing from the input rather than copying out of it, i.e. `&'de str`.
+///
+/// Every [`SerBytes`] type implements this by decoding by value, so borrowed and owned fields can be mixed freely.
+/// Deriving [`SerBytes`] with `#[serbytes(borrow)]` on a type with a lifetime parameter implements this trait instead
+pub trait SerBytesBorrow<'de>: Sized {
+    /// Reads and deserializes the type, borrowing from the buffer where possible
+    fn from_buf_borrowed(buf: &mut ReadByteBufferSlice<'de>) -> BBReadResult<Self>;
+
+    /// Serializes and writes the type to the provided writer
+    fn to_buf_borrowed(&self, buf: &mut dyn WriteByteBuffer);
+
+    /// See [`SerBytes::try_to_buf`]
+    fn try_to_buf_borrowed(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        self.to_buf_borrowed(buf);
+
+        Ok(())
+    }
+
+    /// See [`SerBytes::approx_size`]
+    fn approx_size_borrowed(&self) -> usize {
+        0
+    }
+
+    fn from_bytes_borrowed(bytes: &'de [u8]) -> BBReadResult<Self> {
+        let mut buf = ReadByteBufferSlice::new(bytes);
+
+        Self::from_buf_borrowed(&mut buf)
+    }
+
+    /// Like [`SerBytesBorrow::from_bytes_borrowed`], but errors with [`SpecificError::TrailingBytes`](bytebuffer::prelude::SpecificError::TrailingBytes) if any bytes are left over
+    fn from_bytes_borrowed_exact(bytes: &'de [u8]) -> BBReadResult<Self> {
+        let mut buf = ReadByteBufferSlice::new(bytes);
+
+        let value = Self::from_buf_borrowed(&mut buf)?;
+        buf.ensure_consumed()?;
+
+        Ok(value)
+    }
+
+    fn to_bb_borrowed(&self) -> BBWriteResult<WriteByteBufferOwned> {
+        let mut buf = WriteByteBufferOwned::with_capacity(self.approx_size_borrowed());
+        self.try_to_buf_borrowed(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+impl<'de, S> SerBytesBorrow<'de> for S
+where
+    S: SerBytes,
+{
+    fn from_buf_borrowed(buf: &mut ReadByteBufferSlice<'de>) -> BBReadResult<Self> {
+        S::from_buf(buf)
+    }
+
+    fn to_buf_borrowed(&self, buf: &mut dyn WriteByteBuffer) {
+        self.to_buf(buf);
+    }
+
+    fn try_to_buf_borrowed(&self, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+        self.try_to_buf(buf)
+    }
+
+    fn approx_size_borrowed(&self) -> usize {
+        self.approx_size()
+    }
+}
+
 /// Marker trait that dictates a type will always have the same size when serialized.
 ///
 /// Special care should be taken to ensure this is only implemented on types that will never have a variable serialization size
 ///
 /// Should never be implemented on vectors, maps, enums (unless all enum variants have the exact same size)
-
 pub trait SerBytesStaticSized: SerBytes {}
@@ -1,48 +1,108 @@
+use crate::ser_bytes_impl::LengthLike;
 use crate::ser_trait::SerBytes;
-use bytebuffer::prelude::{BBReadResult, ReadByteBufferRefMut, WriteByteBufferOwned};
+use bytebuffer::prelude::{
+    BBReadResult, ReadByteBuffer, ReadError, SpecificError, WithParent, WriteByteBuffer,
+};
 
-/// Work in progress type
-pub struct Size(usize);
+/// A compact, self describing length
+///
+/// Sizes up to 127 take up a single byte. For larger sizes the number of leading 1 bits in the first
+/// byte is the number of bytes following it, the remaining bits of the first byte are the most
+/// significant bits of the size, i.e.
+///
+/// - `0xxxxxxx` up to 7 bits
+/// - `10xxxxxx xxxxxxxx` up to 14 bits
+/// - `110xxxxx xxxxxxxx xxxxxxxx` up to 21 bits
+/// - ...
+/// - `11111111` followed by 8 bytes for the full 64 bits
+///
+/// The following bytes are always big endian, regardless of the buffer's endianness
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Size(pub usize);
+
+impl Size {
+    /// The number of bytes following the first byte needed to hold `size`
+    fn extra_bytes(size: u64) -> usize {
+        let bits = u64::BITS - size.leading_zeros();
+
+        // Each extra byte holds 8 more bits, but takes a bit away from the first byte
+        (0..8).find(|n| bits <= 7 + 7 * n).unwrap_or(8) as usize
+    }
+}
+
+impl From<usize> for Size {
+    fn from(value: usize) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Size> for usize {
+    fn from(value: Size) -> Self {
+        value.0
+    }
+}
 
 impl SerBytes for Size {
-    fn from_buf(_buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+    fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
+    where
+        R: ReadByteBuffer + ?Sized,
+        Self: Sized,
+    {
+        let mut inner = || {
+            let start = buf.checkpoint();
+            let first = buf.read_u8()?;
+            let extra_bytes = first.leading_ones() as usize;
+
+            let mut size = (first as u64) & (0x7F_u64 >> extra_bytes);
+
+            for byte in buf.read_bytes(extra_bytes)? {
+                size = (size << 8) | *byte as u64;
+            }
+
+            let size = usize::try_from(size)
+                .map_err(|_| ReadError::new(SpecificError::LengthLimit, "usize", None).at(start))?;
+
+            Ok(Self(size))
+        };
+
+        inner().with_parent("Size")
+    }
+
+    fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
+        let size = self.0 as u64;
+        let extra_bytes = Self::extra_bytes(size);
+
+        let mut bytes = [0u8; 9];
+        bytes[1..].copy_from_slice(&size.to_be_bytes());
+
+        let encoded = &mut bytes[8 - extra_bytes..];
+        // Prefix with a 1 bit for each extra byte, followed by a 0 bit if there's room for one
+        encoded[0] |= !(0xFF_u8.checked_shr(extra_bytes as u32).unwrap_or(0));
+
+        buf.write_bytes(encoded);
+    }
+
+    /// A size always takes up at least a byte
+    fn size_hint() -> usize
     where
         Self: Sized,
     {
-        // let is_using_bit_count = buf.read_bool()?;
-        //
-        // let mut total_count = 1usize;
-        // // first bit will always be 1
-        // buf.read_bit()?;
-        //
-        // let mut bit = buf.read_bit()?;
-        //
-        // while bit == 1 {
-        //     total_count |= (total_count << 1);
-        //
-        //     bit = buf.read_bit()?;
-        // }
-        //
-        // let size = if is_using_bit_count {
-        //     let bits_needed = 2usize.pow(total_count as u32);
-        //     buf.read_bits(bits_needed)?
-        // } else {
-        //     let
-        // };
-
-        todo!()
-    }
-
-    fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
-        let size_usize = self.0;
-
-        if size_usize > 0b01111111 {
-            true.to_buf(buf);
-        } else {
-            false.to_buf(buf);
-
-            buf.write_remaining_bits(size_usize as u8)
-                .expect("Should not fail as long as everything is aligned");
-        }
+        1
+    }
+
+    fn approx_size(&self) -> usize {
+        1 + Self::extra_bytes(self.0 as u64)
+    }
+}
+
+impl LengthLike for Size {
+    #[inline(always)]
+    fn from_usize(us: usize) -> Self {
+        Self(us)
+    }
+
+    #[inline(always)]
+    fn to_usize(self) -> usize {
+        self.0
     }
 }
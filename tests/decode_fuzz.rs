@@ -0,0 +1,221 @@
+use serbytes::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A small xorshift generator, so every run exercises the same inputs
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, max: usize) -> usize {
+        (self.next() % max as u64) as usize
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+}
+
+/// Decodes `bytes` as `S` with every reader and packing, starting both aligned and part way into a byte
+fn decode_every_way<S: SerBytes>(bytes: &[u8]) {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        for packing in [Packing::Aligned, Packing::Bitstream] {
+            let _ = S::from_buf(&mut ReadByteBufferSlice::new(bytes).with_packing(packing));
+            let _ = S::from_buf(&mut ReadByteBufferStream::new(bytes).with_packing(packing));
+
+            let mut rbb = ReadByteBufferSlice::new(bytes).with_packing(packing);
+            if rbb.read_bits(3).is_ok() {
+                let _ = S::from_buf(&mut rbb);
+            }
+        }
+    }));
+
+    if result.is_err() {
+        panic!(
+            "Decoding {} panicked on {bytes:02X?}",
+            std::any::type_name::<S>()
+        );
+    }
+}
+
+/// Decodes every truncation of `sample`, the sample with random bytes replaced, and random bytes
+fn fuzz<S: SerBytes>(sample: S, rng: &mut Rng) {
+    let bytes = sample.to_bb().unwrap().into_vec();
+
+    for len in 0..bytes.len() {
+        decode_every_way::<S>(&bytes[..len]);
+    }
+
+    for _ in 0..64 {
+        let mut mutated = bytes.clone();
+
+        for _ in 0..=rng.below(4) {
+            if !mutated.is_empty() {
+                let i = rng.below(mutated.len());
+                mutated[i] = rng.next() as u8;
+            }
+        }
+
+        decode_every_way::<S>(&mutated);
+    }
+
+    for _ in 0..64 {
+        let len = rng.below(48);
+        decode_every_way::<S>(&rng.bytes(len));
+    }
+
+    // Lengths claiming far more data than there is
+    decode_every_way::<S>(&[0xFF; 24]);
+}
+
+#[test]
+fn test_fuzz_primitives() {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+
+    fuzz(true, &mut rng);
+    fuzz(7u8, &mut rng);
+    fuzz(-7i16, &mut rng);
+    fuzz(70_000u32, &mut rng);
+    fuzz(-70_000i64, &mut rng);
+    fuzz(u128::MAX, &mut rng);
+    fuzz(1.5f32, &mut rng);
+    fuzz(-1.5f64, &mut rng);
+    fuzz(Ordering::Greater, &mut rng);
+    fuzz(Duration::new(5, 999_999_999), &mut rng);
+    fuzz(Size(300), &mut rng);
+    fuzz(Var(u64::MAX), &mut rng);
+    fuzz(Var(-300i32), &mut rng);
+}
+
+#[test]
+fn test_fuzz_collections() {
+    let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+
+    fuzz(String::from("hello"), &mut rng);
+    fuzz(vec![1u16, 2, 3], &mut rng);
+    fuzz(VecDeque::from([1u64, 2]), &mut rng);
+    fuzz(HashMap::from([(1u8, String::from("one"))]), &mut rng);
+    fuzz(HashSet::from([1u32, 2]), &mut rng);
+    fuzz(Box::<[u8]>::from([1, 2, 3]), &mut rng);
+    fuzz(Arc::<[u32]>::from([1, 2]), &mut rng);
+    fuzz(Some(5u32), &mut rng);
+    fuzz(U8Vec::<Size>::from(vec![1, 2, 3]), &mut rng);
+    fuzz(LenPrefixed::<Vec<u64>, Size>::new(vec![1, 2]), &mut rng);
+    fuzz(
+        LenPrefixed::<String, Var<u128>>::new("var".into()),
+        &mut rng,
+    );
+    fuzz(SizedBlock::new(vec![1u32, 2]), &mut rng);
+    fuzz(
+        Recoverable::new(vec![SizedBlock::new(String::from("block"))]),
+        &mut rng,
+    );
+    fuzz(ReadError::default().in_field("field"), &mut rng);
+}
+
+#[test]
+fn test_fuzz_derived() {
+    #[derive(SerBytes)]
+    struct Point(u8, u8);
+
+    #[derive(SerBytes)]
+    enum Shape {
+        Empty,
+        Circle { radius: f32 },
+        Polygon(Vec<Point>),
+    }
+
+    #[derive(SerBytes)]
+    struct Scene {
+        name: String,
+        shapes: Vec<Shape>,
+        tags: HashMap<String, Var<u32>>,
+    }
+
+    #[derive(SerBytes)]
+    #[serbytes(tag = varint, delimited)]
+    enum Event {
+        Moved(Point),
+        #[serbytes(other)]
+        Unknown(u64, Vec<u8>),
+    }
+
+    #[derive(SerBytes)]
+    #[serbytes(version = 2)]
+    struct Settings {
+        #[serbytes(until = 2)]
+        volume: u8,
+        #[serbytes(since = 1)]
+        names: Vec<String>,
+    }
+
+    let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+
+    fuzz(
+        Scene {
+            name: "scene".into(),
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle { radius: 2.0 },
+                Shape::Polygon(vec![Point(1, 2)]),
+            ],
+            tags: HashMap::from([("a".into(), Var(3))]),
+        },
+        &mut rng,
+    );
+    fuzz(Event::Moved(Point(3, 4)), &mut rng);
+    fuzz(Event::Unknown(9, vec![1, 2]), &mut rng);
+    fuzz(
+        Versioned::new(Settings {
+            volume: 0,
+            names: vec!["name".into()],
+        }),
+        &mut rng,
+    );
+    fuzz(
+        JsonLikeValue::Array(vec![
+            JsonLikeValue::Number(1.0),
+            JsonLikeValue::String("json".into()),
+            JsonLikeValue::Null,
+        ]),
+        &mut rng,
+    );
+}
+
+#[test]
+fn test_fuzz_borrowed() {
+    let mut rng = Rng(0xA076_1D64_78BD_642F);
+
+    for _ in 0..256 {
+        let len = rng.below(32);
+        let bytes = rng.bytes(len);
+
+        let result = catch_unwind(|| {
+            let _ = <&str>::from_bytes_borrowed(&bytes);
+            let _ = <&[u8]>::from_bytes_borrowed(&bytes);
+        });
+
+        assert!(result.is_ok(), "Borrowed decoding panicked on {bytes:02X?}");
+    }
+}
+
+#[test]
+fn test_deep_nesting_is_an_error() {
+    // Every 3 bytes open another nested array, far deeper than the default depth limit
+    let mut bytes = Vec::new();
+
+    for _ in 0..100_000 {
+        bytes.extend([1, 0, 1]);
+    }
+
+    assert!(JsonLikeValue::from_bytes(&bytes).is_err());
+}
@@ -1,7 +1,11 @@
 use bytebuffer::prelude::{
-    BBReadResult, ReadByteBufferOwned, ReadByteBufferRefMut, WriteByteBufferOwned,
+    BBReadResult, DecodeLimit, DecodeLimits, ReadByteBuffer, ReadByteBufferOwned,
+    ReadByteBufferSlice, ReadError, SpecificError, WriteByteBufferOwned,
 };
 use serbytes::prelude::*;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 
 #[test]
 fn test_may_not_exist() {
@@ -41,7 +45,44 @@ fn test_may_not_exist() {
             f2: u32::default().into(),
             f3: CUSTOM_I32.into(),
         }
-    )
+    );
+
+    // Values are only defaulted at the end of the buffer, on both slices and streams
+    let mut stream = ReadByteBufferStream::new(&[][..]);
+    let value = MayNotExistOrElse::<i32, CustomDataProvider>::from_buf(&mut stream).unwrap();
+    assert_eq!(value.inner, CUSTOM_I32);
+
+    // A value cut off by the end of the buffer is an error, with the read head moved back to its start
+    let mut bytes = FieldsMayNotExist {
+        f1: initial_value,
+        f2: 5.into(),
+        f3: 6.into(),
+    }
+    .to_bb()
+    .unwrap()
+    .into_vec();
+    bytes.pop();
+
+    let err = FieldsMayNotExist::from_bytes(&bytes).expect_err("Truncated trailing u32");
+    assert!(err.specific_error.is_eof());
+
+    let mut rbb = ReadByteBufferSlice::new(&[1, 2]);
+    MayNotExistOrDefault::<u32>::from_buf(&mut rbb).expect_err("Truncated u32");
+    assert_eq!(rbb.position(), 0);
+    assert_eq!(rbb.read_u8().unwrap(), 1);
+
+    let mut stream = ReadByteBufferStream::new(&[1u8, 2][..]);
+    MayNotExistOrElse::<i32, CustomDataProvider>::from_buf(&mut stream).expect_err("Truncated i32");
+    assert_eq!(stream.read_u8().unwrap(), 1);
+
+    // Corrupt data isn't mistaken for missing data
+    let mut bytes = String::from("a").to_bb().unwrap().into_vec();
+    *bytes.last_mut().unwrap() = 0xFF;
+
+    let mut rbb = ReadByteBufferSlice::new(&bytes);
+    let err = MayNotExistOrDefault::<String>::from_buf(&mut rbb).expect_err("Invalid utf8");
+    assert!(err.to_string().contains("invalid utf8"), "{err}");
+    assert_eq!(rbb.position(), 0);
 }
 
 #[test]
@@ -67,7 +108,10 @@ fn test_versioning() {
     impl CurrentVersion for DataVersionTransformerOld {
         type Output = UnversionedRawData1;
 
-        fn get_data_from_buf(&self, buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self::Output> {
+        fn get_data_from_buf<R>(&self, buf: &mut R) -> BBReadResult<Self::Output>
+        where
+            R: ReadByteBuffer + ?Sized,
+        {
             match *self {
                 Self::V1 => UnversionedRawData1::from_buf(buf),
             }
@@ -95,7 +139,10 @@ fn test_versioning() {
     impl CurrentVersion for DataVersionTransformer {
         type Output = UnversionedRawData2;
 
-        fn get_data_from_buf(&self, buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self::Output> {
+        fn get_data_from_buf<R>(&self, buf: &mut R) -> BBReadResult<Self::Output>
+        where
+            R: ReadByteBuffer + ?Sized,
+        {
             match *self {
                 Self::V1 => {
                     let old_data = UnversionedRawData1::from_buf(buf)?;
@@ -124,7 +171,7 @@ fn test_versioning() {
 
     let data1_versioned = VersionedData1::new(data1.clone());
 
-    let wbb = data1_versioned.to_bb();
+    let wbb = data1_versioned.to_bb().unwrap();
 
     // println!("{:?}", wbb.buf());
 
@@ -163,8 +210,9 @@ fn test_block() {
     struct BadType;
 
     impl SerBytes for BadType {
-        fn from_buf(buf: &mut ReadByteBufferRefMut) -> BBReadResult<Self>
+        fn from_buf<R>(buf: &mut R) -> BBReadResult<Self>
         where
+            R: ReadByteBuffer + ?Sized,
             Self: Sized,
         {
             buf.read_bytes(10)?;
@@ -172,7 +220,7 @@ fn test_block() {
             Ok(Self)
         }
 
-        fn to_buf(&self, buf: &mut WriteByteBufferOwned) {
+        fn to_buf(&self, buf: &mut dyn WriteByteBuffer) {
             buf.write_bytes(&[0; 5]);
         }
     }
@@ -243,3 +291,658 @@ fn dyn_compat() {
 
     assert_eq!(s, s_deser);
 }
+
+#[test]
+fn test_from_reader() {
+    let first = String::from("first value");
+    let second = vec![3u64, 1, 4];
+
+    let mut bytes = first.to_bb().unwrap().into_vec();
+    bytes.extend(second.to_bb().unwrap().into_vec());
+
+    let mut reader = bytes.as_slice();
+
+    let first_read = String::from_reader(&mut reader).expect("Read first value from reader");
+    let second_read = Vec::<u64>::from_reader(&mut reader).expect("Read second value from reader");
+
+    assert_eq!(first, first_read);
+    assert_eq!(second, second_read);
+    assert!(reader.is_empty());
+
+    u8::from_reader(&mut reader).expect_err("Reader should be exhausted");
+}
+
+#[test]
+fn test_to_writer() {
+    #[derive(SerBytes, Debug, PartialEq)]
+    struct Record {
+        name: String,
+        values: Vec<f64>,
+        flag: bool,
+    }
+
+    let record = Record {
+        name: "streamed".to_string(),
+        values: vec![1.5, -2.25, 1e10],
+        flag: true,
+    };
+
+    let mut sink = Vec::new();
+
+    record
+        .to_writer(&mut sink)
+        .expect("Writing to a vec can't fail");
+
+    assert_eq!(sink, record.to_bb().unwrap().into_vec());
+
+    let record_read = Record::from_reader(sink.as_slice()).expect("Read record back");
+
+    assert_eq!(record, record_read);
+}
+
+#[test]
+fn test_to_slice() {
+    let values: Vec<u32> = vec![1, 2, 3, 4];
+    let expected = values.to_bb().unwrap().into_vec();
+
+    let mut exact = vec![0; expected.len()];
+    let len = values.to_slice(&mut exact).expect("Slice is large enough");
+
+    assert_eq!(len, expected.len());
+    assert_eq!(exact, expected);
+
+    let mut small = [0; 8];
+    let err = values.to_slice(&mut small).expect_err("Slice is too small");
+
+    let WriteError::SliceOverflow(err) = err else {
+        panic!("Expected a slice overflow, got {err}");
+    };
+
+    assert_eq!(err.required, expected.len());
+    assert_eq!(err.capacity, 8);
+}
+
+#[test]
+fn test_borrowed_matches_owned() {
+    let string = String::from("borrowed");
+    let bytes = vec![5u8, 6, 7];
+
+    let string_bb = string.to_bb().unwrap().into_vec();
+    let bytes_bb = bytes.to_bb().unwrap().into_vec();
+
+    assert_eq!(string_bb, "borrowed".to_bb_borrowed().unwrap().into_vec());
+    assert_eq!(
+        bytes_bb,
+        bytes.as_slice().to_bb_borrowed().unwrap().into_vec()
+    );
+
+    assert_eq!(
+        <&str>::from_bytes_borrowed(&string_bb).expect("Borrow str"),
+        "borrowed"
+    );
+    assert_eq!(
+        <&[u8]>::from_bytes_borrowed(&bytes_bb).expect("Borrow bytes"),
+        &[5, 6, 7]
+    );
+
+    let invalid_utf8 = [0, 2, 0xC3, 0x28];
+    assert!(<&str>::from_bytes_borrowed(&invalid_utf8).is_err());
+}
+
+#[test]
+fn test_var() {
+    #[derive(SerBytes, Debug)]
+    struct Compact {
+        id: Var<u64>,
+        delta: Var<i32>,
+        bytes: U8Vec<Var<u32>>,
+    }
+
+    let compact = Compact {
+        id: Var(5),
+        delta: Var(-70),
+        bytes: U8Vec::from_vec(vec![1, 2, 3]),
+    };
+
+    let bb = compact.to_bb().unwrap().into_vec();
+
+    // 1 byte id, 2 byte delta, 1 byte length
+    assert_eq!(bb.len(), 1 + 2 + 1 + 3);
+    let read = Compact::from_bytes(&bb).expect("Read compact");
+
+    assert_eq!(read.id, compact.id);
+    assert_eq!(read.delta, compact.delta);
+    assert_eq!(read.bytes.vec, compact.bytes.vec);
+
+    for value in [0, 1, 127, 128, 16383, 16384, u64::MAX] {
+        assert_eq!(Var(value).approx_size(), Var(value).to_bb().unwrap().len());
+    }
+
+    for value in [0, -1, 63, -64, 64, -65, i64::MIN, i64::MAX] {
+        assert_eq!(Var(value).approx_size(), Var(value).to_bb().unwrap().len());
+    }
+}
+
+#[test]
+fn test_size() {
+    assert_eq!(Size(0).to_bb().unwrap().into_vec(), vec![0x00]);
+    assert_eq!(Size(127).to_bb().unwrap().into_vec(), vec![0x7F]);
+    assert_eq!(Size(128).to_bb().unwrap().into_vec(), vec![0x80, 0x80]);
+    assert_eq!(Size(0x3FFF).to_bb().unwrap().into_vec(), vec![0xBF, 0xFF]);
+    assert_eq!(
+        Size(0x4000).to_bb().unwrap().into_vec(),
+        vec![0xC0, 0x40, 0x00]
+    );
+    assert_eq!(
+        Size(usize::MAX).to_bb().unwrap().into_vec(),
+        vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    );
+
+    let mut sizes = vec![0, 1, usize::MAX, usize::MAX - 1];
+
+    for bits in 1..usize::BITS {
+        sizes.extend([(1 << bits) - 1, 1 << bits, (1 << bits) + 1]);
+    }
+
+    for size in sizes {
+        let bb = Size(size).to_bb().unwrap().into_vec();
+
+        assert_eq!(Size(size).approx_size(), bb.len(), "Size of {size}");
+        assert_eq!(Size::from_bytes(&bb).expect("Read size"), Size(size));
+    }
+
+    // Truncated size
+    Size::from_bytes(&[0xC0, 0x40]).expect_err("Missing a byte");
+
+    #[derive(SerBytes, Debug)]
+    struct Lengths {
+        bytes: U8Vec<Size>,
+        block: SizedBlock<u32, Size>,
+    }
+
+    let sized = Lengths {
+        bytes: U8Vec::from_vec(vec![7; 200]),
+        block: SizedBlock::new_with_len_type(5),
+    };
+
+    let bb = sized.to_bb().unwrap().into_vec();
+
+    assert_eq!(bb.len(), 2 + 200 + 1 + 4);
+
+    let read = Lengths::from_bytes(&bb).expect("Read lengths");
+
+    assert_eq!(read.bytes.vec, sized.bytes.vec);
+    assert_eq!(read.block.inner, 5);
+}
+
+#[test]
+fn test_len_prefixed() {
+    // More elements than a u16 can count
+    let large = (0..70_000u32).collect::<Vec<_>>();
+
+    let prefixed = LenPrefixed::<_, u32>::new(large.clone());
+    let bb = prefixed.to_bb().unwrap().into_vec();
+
+    assert_eq!(bb.len(), 4 + 70_000 * 4);
+    assert_eq!(&bb[..4], &70_000u32.to_be_bytes());
+    assert_eq!(prefixed.approx_size(), bb.len());
+    assert_eq!(
+        LenPrefixed::<Vec<u32>, u32>::from_bytes(&bb)
+            .expect("Read large vec")
+            .inner,
+        large
+    );
+
+    // The default length is the same u16 the collections use
+    let vec = vec![1u16, 2, 3];
+    assert_eq!(
+        LenPrefixed::<_>::new(vec.clone())
+            .to_bb()
+            .unwrap()
+            .into_vec(),
+        vec.to_bb().unwrap().into_vec()
+    );
+
+    #[derive(SerBytes, Debug, PartialEq)]
+    struct Collections {
+        string: LenPrefixed<String, Size>,
+        deque: LenPrefixed<VecDeque<i16>, u8>,
+        map: LenPrefixed<HashMap<u8, String>, Var<u32>>,
+        set: LenPrefixed<HashSet<u64>, u32>,
+        boxed: LenPrefixed<Box<[u8]>, u64>,
+        arc: LenPrefixed<Arc<[u32]>, Size>,
+    }
+
+    let collections = Collections {
+        string: "a".repeat(300).into(),
+        deque: VecDeque::from([-1, 0, 1]).into(),
+        map: HashMap::from([(1, "one".to_string()), (2, "two".to_string())]).into(),
+        set: HashSet::from([5, 6]).into(),
+        boxed: vec![9; 3].into_boxed_slice().into(),
+        arc: Arc::<[u32]>::from([1, 2]).into(),
+    };
+
+    let bb = collections.to_bb().unwrap().into_vec();
+
+    // Size of 300 is 2 bytes
+    assert_eq!(&bb[..2], &[0x81, 0x2C]);
+    assert_eq!(
+        Collections::from_bytes(&bb).expect("Read collections"),
+        collections
+    );
+}
+
+#[test]
+fn test_write_errors() {
+    // Lengths which don't fit are errors rather than being truncated
+    let err = vec![0u8; u16::MAX as usize + 1]
+        .to_bb()
+        .expect_err("Length doesn't fit into a u16");
+
+    assert!(matches!(
+        err,
+        WriteError::LengthOverflow {
+            len: 65536,
+            length_type: "u16"
+        }
+    ));
+
+    LenPrefixed::<_, u8>::new("a".repeat(256))
+        .to_bb()
+        .expect_err("Length doesn't fit into a u8");
+
+    LenPrefixed::<_, u8>::new("a".repeat(255))
+        .to_bb()
+        .expect("Length fits into a u8");
+
+    let err = BBReadResult::<u8>::Err(ReadError::default())
+        .to_bb()
+        .expect_err("Err variant can't be written");
+
+    assert!(matches!(err, WriteError::InvalidState(_)));
+
+    #[derive(SerBytes, Debug)]
+    struct Shared {
+        id: u8,
+        cells: Vec<RefCell<u32>>,
+    }
+
+    let shared = Shared {
+        id: 1,
+        cells: vec![RefCell::new(1), RefCell::new(2)],
+    };
+
+    let borrowed = shared.cells[1].borrow_mut();
+
+    // The error propagates out of the derived impl, and out of every sink
+    let err = shared.to_bb().expect_err("RefCell is mutably borrowed");
+    assert!(matches!(err, WriteError::InvalidState(_)));
+
+    shared
+        .to_writer(Vec::new())
+        .expect_err("RefCell is mutably borrowed");
+    shared
+        .to_slice(&mut [0; 64])
+        .expect_err("RefCell is mutably borrowed");
+
+    drop(borrowed);
+
+    assert_eq!(
+        shared.to_bb().expect("RefCell is free").into_vec(),
+        vec![1, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Failed to serialize")]
+fn test_to_buf_panics_on_write_error() {
+    let cell = RefCell::new(0u8);
+    let _borrowed = cell.borrow_mut();
+
+    let mut wbb = WriteByteBufferOwned::new();
+    cell.to_buf(&mut wbb);
+}
+
+#[test]
+fn test_decode_limits() {
+    fn from_bytes_limited<S: SerBytes>(bytes: &[u8], limits: DecodeLimits) -> BBReadResult<S> {
+        S::from_buf(&mut ReadByteBufferSlice::new(bytes).with_decode_limits(limits))
+    }
+
+    fn exceeded(err: ReadError, expected: DecodeLimit) -> bool {
+        matches!(err.specific_error, SpecificError::LimitExceeded { limit, .. } if limit == expected)
+    }
+
+    // A length claiming far more items than there are bytes is rejected before allocating
+    let lying_len = [0xFF, 0xFF, 0, 0, 0, 1];
+    let limits = DecodeLimits::UNLIMITED.with_max_collection_len(100);
+
+    let err = from_bytes_limited::<Vec<u32>>(&lying_len, limits).expect_err("Too long");
+    assert!(exceeded(err, DecodeLimit::CollectionLen));
+
+    let err = from_bytes_limited::<HashMap<u8, u8>>(&lying_len, limits).expect_err("Too long");
+    assert!(exceeded(err, DecodeLimit::CollectionLen));
+
+    let err = from_bytes_limited::<U8Vec>(&lying_len, limits).expect_err("Too long");
+    assert!(exceeded(err, DecodeLimit::CollectionLen));
+
+    // Strings are limited separately
+    let string = "a".repeat(50).to_bb().unwrap().into_vec();
+    let limits = DecodeLimits::UNLIMITED.with_max_string_len(49);
+
+    let err = from_bytes_limited::<String>(&string, limits).expect_err("Too long");
+    assert!(exceeded(err, DecodeLimit::StringLen));
+
+    let mut borrowed = ReadByteBufferSlice::new(&string).with_decode_limits(limits);
+    let err = <&str>::from_buf_borrowed(&mut borrowed).expect_err("Too long");
+    assert!(exceeded(err, DecodeLimit::StringLen));
+
+    from_bytes_limited::<String>(&string, limits.with_max_string_len(50)).expect("Fits");
+
+    // Allocations add up across every collection, including those in sized blocks
+    #[derive(SerBytes, Debug)]
+    struct Vecs(Vec<u64>, SizedBlock<Vec<u64>>, Vec<u64>);
+
+    let bytes = Vecs(vec![1; 8], SizedBlock::new(vec![2; 8]), vec![3; 8])
+        .to_bb()
+        .unwrap()
+        .into_vec();
+
+    let err =
+        from_bytes_limited::<Vecs>(&bytes, DecodeLimits::UNLIMITED.with_max_alloc(64 * 3 - 1))
+            .expect_err("Out of allocation");
+    assert!(exceeded(err, DecodeLimit::Alloc));
+
+    from_bytes_limited::<Vecs>(&bytes, DecodeLimits::UNLIMITED.with_max_alloc(64 * 3))
+        .expect("Exactly fits");
+
+    // Recursive types are bounded in depth, [[[[...]]]]
+    let mut nested = Vec::new();
+    for _ in 0..10_000 {
+        nested.extend([1, 0, 1]);
+    }
+    nested.push(5);
+
+    let limits = DecodeLimits::UNLIMITED.with_max_depth(64);
+
+    let Err(err) = from_bytes_limited::<JsonLikeValue>(&nested, limits) else {
+        panic!("Too deep");
+    };
+    assert!(exceeded(err, DecodeLimit::Depth));
+
+    let shallow = &nested[nested.len() - 3 * 63 - 1..];
+    assert!(from_bytes_limited::<JsonLikeValue>(shallow, limits).is_ok());
+
+    // Recursive errors are bounded too
+    let mut error = ReadError::default();
+    for _ in 0..10 {
+        error = error.new_parent("Parent");
+    }
+
+    let bytes = error.to_bb().unwrap().into_vec();
+
+    let err = from_bytes_limited::<ReadError>(&bytes, DecodeLimits::UNLIMITED.with_max_depth(5))
+        .expect_err("Too deep");
+    assert!(exceeded(err, DecodeLimit::Depth));
+}
+
+#[test]
+fn test_read_error_kinds() {
+    // Invalid utf8 is reported at the first invalid byte
+    let bytes = [0, 4, b'a', b'b', 0xFF, b'c'];
+    let err = String::from_bytes(&bytes).expect_err("Invalid utf8");
+    assert_eq!(err.specific_error, SpecificError::InvalidUtf8);
+    assert_eq!(err.position.map(|position| position.byte), Some(4));
+
+    let err = <&str>::from_bytes_borrowed(&bytes).expect_err("Invalid utf8");
+    assert_eq!(err.specific_error, SpecificError::InvalidUtf8);
+    assert_eq!(err.position.map(|position| position.byte), Some(4));
+
+    let err = std::cmp::Ordering::from_bytes(&[3]).expect_err("Invalid ordinal");
+    assert_eq!(
+        err.specific_error,
+        SpecificError::EnumOrdinalOutOfBounds {
+            max_bound: 2,
+            got: 3
+        }
+    );
+
+    // A length which can't be a usize
+    let bytes = Var(u128::MAX).to_bb().unwrap().into_vec();
+    let err = LenPrefixed::<Vec<u8>, Var<u128>>::from_bytes(&bytes).expect_err("Length too large");
+    assert_eq!(err.specific_error, SpecificError::LengthLimit);
+    assert_eq!(err.position.map(|position| position.byte), Some(0));
+
+    // Positions within a sized block are relative to the whole buffer
+    #[derive(SerBytes, Debug)]
+    struct Named {
+        id: u8,
+        name: String,
+    }
+
+    #[derive(SerBytes, Debug)]
+    struct Blocked {
+        first: u32,
+        block: SizedBlock<Named>,
+    }
+
+    let mut bytes = Blocked {
+        first: 1,
+        block: SizedBlock::new(Named {
+            id: 2,
+            name: "ab".into(),
+        }),
+    }
+    .to_bb()
+    .unwrap()
+    .into_vec();
+
+    bytes[10] = 0xFF;
+
+    let err = Blocked::from_bytes(&bytes).expect_err("Invalid utf8");
+    assert_eq!(err.specific_error, SpecificError::InvalidUtf8);
+    assert_eq!(err.position.map(|position| position.byte), Some(10));
+    assert!(err.to_string().contains("at byte 10"));
+
+    // Custom errors keep their source and are written as their message
+    let err = ReadError::custom("Checksum mismatch", "Save file");
+    assert!(std::error::Error::source(&err.specific_error).is_some());
+
+    let read_back = ReadError::from_bytes(&err.to_bb().unwrap().into_vec()).unwrap();
+    let SpecificError::Custom(custom) = read_back.specific_error else {
+        panic!("Expected a custom error");
+    };
+    assert_eq!(custom.to_string(), "Checksum mismatch");
+}
+
+#[test]
+fn test_exact() {
+    let mut bytes = 7u16.to_bb().unwrap().into_vec();
+
+    assert_eq!(u16::from_bytes_exact(&bytes).unwrap(), 7);
+    assert_eq!(u16::from_vec_exact(bytes.clone()).unwrap(), 7);
+    assert_eq!(
+        <&[u8]>::from_bytes_borrowed_exact(&[0, 1, 9]).unwrap(),
+        &[9]
+    );
+
+    bytes.extend([1, 2, 3]);
+
+    // The lenient entry points ignore the trailing data
+    assert_eq!(u16::from_bytes(&bytes).unwrap(), 7);
+
+    let err = u16::from_bytes_exact(&bytes).expect_err("Trailing bytes");
+    assert_eq!(err.specific_error, SpecificError::TrailingBytes(3));
+    assert_eq!(err.position.map(|position| position.byte), Some(2));
+
+    let err = u16::from_vec_exact(bytes).expect_err("Trailing bytes");
+    assert_eq!(err.specific_error, SpecificError::TrailingBytes(3));
+
+    let err = <&[u8]>::from_bytes_borrowed_exact(&[0, 1, 9, 9]).expect_err("Trailing bytes");
+    assert_eq!(err.specific_error, SpecificError::TrailingBytes(1));
+
+    // Leftover bits of a partially read byte aren't trailing data
+    assert!(bool::from_bytes_exact(&[1]).is_ok());
+
+    let mut stream = ReadByteBufferStream::new(&[1u8, 2][..]);
+    stream.read_u8().unwrap();
+    assert!(stream.ensure_consumed().is_err());
+    stream.read_u8().unwrap();
+    assert!(stream.ensure_consumed().is_ok());
+}
+
+#[test]
+fn test_read_all() {
+    let mut wbb = WriteByteBufferOwned::new();
+    for value in [1u32, 2, 3] {
+        value.to_buf(&mut wbb);
+    }
+    let bytes = wbb.into_vec();
+
+    let mut rbb = ReadByteBufferSlice::new(&bytes);
+    let values = rbb.read_all::<u32>().collect::<BBReadResult<Vec<_>>>();
+    assert_eq!(values.unwrap(), vec![1, 2, 3]);
+
+    let mut stream = ReadByteBufferStream::new(&bytes[..]);
+    let values = stream.read_all::<u32>().collect::<BBReadResult<Vec<_>>>();
+    assert_eq!(values.unwrap(), vec![1, 2, 3]);
+
+    assert_eq!(ReadByteBufferSlice::new(&[]).read_all::<u32>().count(), 0);
+
+    // A value cut off part way through is an error, after which the iterator stops
+    let mut rbb = ReadByteBufferSlice::new(&bytes[..10]);
+    let mut values = rbb.read_all::<u32>();
+    assert_eq!(values.next().unwrap().unwrap(), 1);
+    assert_eq!(values.next().unwrap().unwrap(), 2);
+    assert!(values.next().unwrap().unwrap_err().specific_error.is_eof());
+    assert!(values.next().is_none());
+
+    // Values taking up no space would never reach the end
+    let mut rbb = ReadByteBufferSlice::new(&[1]);
+    let mut values = rbb.read_all::<()>();
+    assert_eq!(
+        values.next().unwrap().unwrap_err().specific_error,
+        SpecificError::NoProgress
+    );
+    assert!(values.next().is_none());
+}
+
+#[test]
+fn test_recoverable() {
+    #[derive(SerBytes, Debug, Clone, Eq, PartialEq)]
+    struct Entry {
+        id: u8,
+        name: String,
+    }
+
+    let entries = (0..3)
+        .map(|id| {
+            SizedBlock::new(Entry {
+                id,
+                name: "abc".into(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut bytes = entries.to_bb().unwrap().into_vec();
+
+    // Each block is a u16 length, the id, a u16 string length and the string, after the u16 vec length
+    let corrupt_byte = 2 + 8 + 5;
+    bytes[corrupt_byte] = 0xFF;
+
+    assert!(Vec::<SizedBlock<Entry>>::from_bytes(&bytes).is_err());
+
+    let recovered = Recoverable::<Vec<SizedBlock<Entry>>>::from_bytes_exact(&bytes).unwrap();
+    assert!(!recovered.is_intact());
+    assert_eq!(
+        recovered.inner,
+        vec![entries[0].clone(), entries[2].clone()]
+    );
+
+    let [err] = &recovered.errors[..] else {
+        panic!("Expected a single error, got {:?}", recovered.errors);
+    };
+    assert_eq!(err.specific_error, SpecificError::InvalidUtf8);
+    assert_eq!(
+        err.position.map(|position| position.byte),
+        Some(corrupt_byte)
+    );
+    assert!(
+        err.to_string().starts_with("[1].name: invalid utf8"),
+        "{err}"
+    );
+
+    // Written exactly the same as the collection it wraps
+    assert_eq!(
+        Recoverable::new(entries.clone())
+            .to_bb()
+            .unwrap()
+            .into_vec(),
+        entries.to_bb().unwrap().into_vec()
+    );
+
+    let prefixed = LenPrefixed::<_, Var<u32>>::new(entries.clone());
+    let recovered = Recoverable::<LenPrefixed<Vec<SizedBlock<Entry>>, Var<u32>>>::from_bytes(
+        &prefixed.to_bb().unwrap().into_vec(),
+    )
+    .unwrap();
+    assert!(recovered.is_intact());
+    assert_eq!(recovered.inner.inner, entries);
+
+    // Blocks can't be skipped past once the buffer ends
+    assert!(
+        Recoverable::<Vec<SizedBlock<Entry>>>::from_bytes(&bytes[..bytes.len() - 1])
+            .unwrap_err()
+            .specific_error
+            .is_eof()
+    );
+}
+
+#[test]
+fn test_byte_bool() {
+    assert_eq!(ByteBool(true).to_bb().unwrap().into_vec(), vec![1]);
+    assert_eq!(ByteBool::from_bytes(&[0]).unwrap(), ByteBool(false));
+    assert_eq!(ByteBool::from_bytes(&[1]).unwrap(), ByteBool(true));
+
+    let err = ByteBool::from_bytes(&[2]).unwrap_err();
+    assert_eq!(err.specific_error, SpecificError::InvalidBool(2));
+
+    assert!(
+        ByteBool::from_bytes(&[])
+            .unwrap_err()
+            .specific_error
+            .is_eof()
+    );
+}
+
+#[test]
+fn test_to_buf_with() {
+    #[derive(SerBytes)]
+    struct Mixed {
+        values: Vec<u32>,
+        var: Var<i64>,
+        flag: bool,
+        byte_flag: ByteBool,
+    }
+
+    let mixed = Mixed {
+        values: vec![1, 70000, u32::MAX],
+        var: Var(-300),
+        flag: true,
+        byte_flag: ByteBool(true),
+    };
+
+    let mut direct = WriteByteBufferOwned::new();
+    mixed.values.to_buf_with(&mut direct).unwrap();
+    mixed.var.to_buf_with(&mut direct).unwrap();
+    mixed.flag.to_buf_with(&mut direct).unwrap();
+    mixed.byte_flag.to_buf_with(&mut direct).unwrap();
+
+    assert_eq!(direct.buf(), mixed.to_bb().unwrap().buf());
+
+    let mut slice = [0; 32];
+    let mut wbb = WriteByteBufferSlice::new(&mut slice);
+    mixed.to_buf_with(&mut wbb).unwrap();
+
+    assert_eq!(wbb.buf(), direct.buf());
+}
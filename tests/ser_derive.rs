@@ -1,4 +1,5 @@
 use serbytes::prelude::*;
+use std::borrow::Cow;
 
 #[test]
 fn test_struct_derive() {
@@ -15,7 +16,7 @@ fn test_struct_derive() {
         f3: 67,
     };
 
-    let wbb = b.to_bb();
+    let wbb = b.to_bb().unwrap();
 
     let mut rbb: ReadByteBufferOwned = wbb.into();
 
@@ -133,11 +134,11 @@ fn test_generic_struct() {
     };
 
     let numbered_2 = NumberedGen::from_buf(
-        &mut ReadByteBufferOwned::from_vec(numbered.to_bb().into_vec()).rbb_ref_mut(),
+        &mut ReadByteBufferOwned::from_vec(numbered.to_bb().unwrap().into_vec()).rbb_ref_mut(),
     )
     .expect("Deserialize numbered generic");
     let stringed_2 = StringedGen::from_buf(
-        &mut ReadByteBufferOwned::from_vec(stringed.to_bb().into_vec()).rbb_ref_mut(),
+        &mut ReadByteBufferOwned::from_vec(stringed.to_bb().unwrap().into_vec()).rbb_ref_mut(),
     )
     .expect("Deserialize stringed generic");
 
@@ -204,3 +205,716 @@ fn test_generic_enum() {
     assert_eq!(stringed_first, deserialized_stringed_first);
     assert_eq!(stringed_second, deserialized_stringed_second);
 }
+
+#[test]
+fn test_derive_little_endian() {
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    struct Packet {
+        id: u16,
+        payload: Vec<i32>,
+    }
+
+    let packet = Packet {
+        id: 0x0A0B,
+        payload: vec![-2, 70000],
+    };
+
+    let mut wbb = WriteByteBufferOwned::new().with_endianness(Endianness::Little);
+    packet.to_buf(&mut wbb);
+
+    assert_eq!(&wbb.buf()[..2], &[0x0B, 0x0A]);
+
+    let mut rbb = ReadByteBufferOwned::from_vec(wbb).with_endianness(Endianness::Little);
+
+    let deserialized = Packet::from_buf(&mut rbb.rbb_ref_mut()).expect("Deserialize little endian");
+
+    assert_eq!(packet, deserialized);
+}
+
+#[test]
+fn test_derive_from_any_reader() {
+    #[derive(SerBytes, Debug, PartialEq)]
+    struct Entry {
+        id: u32,
+        name: String,
+    }
+
+    let entry = Entry {
+        id: 17,
+        name: "entry".to_string(),
+    };
+
+    let mut rbb = ReadByteBufferOwned::from(entry.to_bb().unwrap());
+
+    let peeked = Entry::from_buf(&mut rbb.peek()).expect("Deserialize from peeked buffer");
+    let owned = Entry::from_buf(&mut rbb).expect("Deserialize from owned buffer");
+
+    assert_eq!(peeked, entry);
+    assert_eq!(owned, entry);
+}
+
+#[test]
+fn test_derive_borrowed() {
+    #[derive(SerBytes, Debug, PartialEq)]
+    #[serbytes(borrow)]
+    struct Message<'a> {
+        id: u32,
+        topic: &'a str,
+        payload: &'a [u8],
+        note: Cow<'a, str>,
+    }
+
+    #[derive(SerBytes, Debug, PartialEq)]
+    #[serbytes(borrow)]
+    enum Event<'a> {
+        Empty,
+        Named { name: &'a str },
+        Message(Message<'a>),
+    }
+
+    let message = Message {
+        id: 3,
+        topic: "status",
+        payload: &[1, 2, 3, 4],
+        note: Cow::Borrowed("all good"),
+    };
+
+    let bytes = message.to_bb_borrowed().unwrap().into_vec();
+    let read = Message::from_bytes_borrowed(&bytes).expect("Deserialize borrowed message");
+
+    assert_eq!(read, message);
+    assert!(matches!(read.note, Cow::Borrowed(_)));
+
+    // The borrowed fields point into the input rather than into a copy of it
+    let input = bytes.as_ptr_range();
+    assert!(input.contains(&read.topic.as_ptr()));
+    assert!(input.contains(&read.payload.as_ptr()));
+
+    let events = [
+        Event::Empty,
+        Event::Named { name: "event" },
+        Event::Message(message),
+    ];
+
+    for event in events {
+        let bytes = event.to_bb_borrowed().unwrap().into_vec();
+
+        assert_eq!(
+            Event::from_bytes_borrowed(&bytes).expect("Deserialize borrowed event"),
+            event
+        );
+    }
+}
+
+#[test]
+fn test_derive_lifetime_owned() {
+    // Without `borrow`, a lifetime parameter doesn't change the derived trait
+    #[derive(SerBytes, Debug, PartialEq)]
+    struct Msg<'a> {
+        name: Cow<'a, str>,
+    }
+
+    #[derive(SerBytes, Debug, PartialEq)]
+    struct Inbox<'a> {
+        messages: Vec<Msg<'a>>,
+    }
+
+    let inbox = Inbox {
+        messages: vec![
+            Msg {
+                name: Cow::Borrowed("first"),
+            },
+            Msg {
+                name: Cow::Owned("second".into()),
+            },
+        ],
+    };
+
+    let bytes = inbox.to_bb().unwrap().into_vec();
+    assert_eq!(Inbox::from_bytes(&bytes).unwrap(), inbox);
+}
+
+#[test]
+fn test_derive_bitstream() {
+    #[derive(SerBytes, Debug, PartialEq)]
+    struct Flags {
+        a: bool,
+        b: bool,
+        c: bool,
+        value: u8,
+        name: String,
+    }
+
+    let flags = Flags {
+        a: true,
+        b: false,
+        c: true,
+        value: 200,
+        name: "packed".to_string(),
+    };
+
+    let mut wbb = WriteByteBufferOwned::new().with_packing(Packing::Bitstream);
+    flags.to_buf(&mut wbb);
+
+    // The 3 bools only take up 3 bits, rather than a byte of their own
+    assert_eq!(wbb.len(), 1 + 2 + 6 + 1);
+    assert_eq!(flags.to_bb().unwrap().len(), 2 + 2 + 6);
+
+    let bytes = wbb.into_vec();
+    let mut rbb = ReadByteBufferSlice::new(&bytes).with_packing(Packing::Bitstream);
+
+    assert_eq!(
+        Flags::from_buf(&mut rbb).expect("Deserialize bitstream"),
+        flags
+    );
+}
+
+#[test]
+fn test_error_path() {
+    #[derive(SerBytes, Debug)]
+    enum Item {
+        Empty,
+        Stack { id: u16, count: u8 },
+        Named(String),
+    }
+
+    #[derive(SerBytes, Debug)]
+    struct Player {
+        name: String,
+        inventory: Vec<Item>,
+    }
+
+    #[derive(SerBytes, Debug)]
+    struct Config {
+        version: u8,
+        players: Vec<Player>,
+    }
+
+    let player = |name: &str| Player {
+        name: name.into(),
+        inventory: vec![Item::Empty, Item::Stack { id: 4, count: 2 }],
+    };
+
+    let config = Config {
+        version: 1,
+        players: vec![player("a"), player("b")],
+    };
+
+    let bytes = config.to_bb().unwrap().into_vec();
+
+    // Cut off within the count of the last player's stack
+    let err = Config::from_bytes(&bytes[..bytes.len() - 1]).expect_err("Truncated");
+
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Config.players[1].inventory[1]::Stack.count: unexpected EOF reading a byte at byte {}",
+            bytes.len() - 1
+        )
+    );
+
+    // Tuple fields are named by their index, and the outermost type is kept
+    let mut bytes = Item::Named("ok".into()).to_bb().unwrap().into_vec();
+    bytes[3] = 0xFF;
+
+    let err = Item::from_bytes(&bytes).expect_err("Invalid utf8");
+    assert_eq!(err.to_string(), "Item::Named.0: invalid utf8 at byte 3");
+
+    let err = Item::from_bytes(&[7]).expect_err("Invalid ordinal");
+    assert_eq!(err.path.segments, vec![PathSegment::Type("Item".into())]);
+}
+
+#[test]
+fn test_field_attributes() {
+    /// Not `SerBytes`, so it can only be skipped
+    #[derive(Debug, Default, Eq, PartialEq)]
+    struct Cache(Vec<u8>);
+
+    fn unloaded() -> Option<u32> {
+        Some(404)
+    }
+
+    /// Writes an `i32` as a single byte, erroring if it doesn't fit
+    mod as_byte {
+        use serbytes::prelude::*;
+
+        pub fn value_from_buf<R>(buf: &mut R) -> BBReadResult<i32>
+        where
+            R: ReadByteBuffer + ?Sized,
+        {
+            u8::from_buf(buf).map(i32::from)
+        }
+
+        pub fn try_value_to_buf(value: &i32, buf: &mut dyn WriteByteBuffer) -> BBWriteResult<()> {
+            let byte = u8::try_from(*value)
+                .map_err(|_| WriteError::invalid_state("level doesn't fit into a byte"))?;
+
+            byte.try_to_buf(buf)
+        }
+    }
+
+    /// Writes a `u32` as a `u16`, truncating it
+    struct AsU16;
+
+    impl MappedDataProvider<u32> for AsU16 {
+        fn value_from_buf<R>(buf: &mut R) -> BBReadResult<u32>
+        where
+            R: ReadByteBuffer + ?Sized,
+        {
+            u16::from_buf(buf).map(u32::from)
+        }
+
+        fn value_to_buf(value: &u32, buf: &mut dyn WriteByteBuffer) {
+            (*value as u16).to_buf(buf);
+        }
+    }
+
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    struct Save {
+        name: String,
+        #[serbytes(skip)]
+        cache: Cache,
+        #[serbytes(skip, default = "unloaded")]
+        loaded: Option<u32>,
+        #[serbytes(with = "as_byte")]
+        level: i32,
+        #[serbytes(len = u8)]
+        items: Vec<u16>,
+        #[serbytes(with = "AsU16")]
+        rank: u32,
+    }
+
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    enum Message {
+        Chat(#[serbytes(len = Var<u32>)] String, #[serbytes(skip)] Cache),
+    }
+
+    let save = Save {
+        name: "save".into(),
+        cache: Cache(vec![1, 2, 3]),
+        loaded: None,
+        level: 12,
+        items: vec![1, 2],
+        rank: 3,
+    };
+
+    let bytes = save.to_bb().unwrap().into_vec();
+
+    // u16 length + name, a single byte level, u8 length + items, a u16 rank
+    assert_eq!(bytes.len(), 2 + 4 + 1 + 1 + 4 + 2);
+    assert_eq!(&bytes[6..8], &[12, 2]);
+    // The size of the `with` fields isn't known
+    assert_eq!(save.approx_size(), 2 + 4 + 1 + 4);
+    assert_eq!(Save::size_hint(), 2 + 1);
+
+    assert_eq!(
+        Save::from_bytes_exact(&bytes).unwrap(),
+        Save {
+            cache: Cache::default(),
+            loaded: Some(404),
+            ..save
+        }
+    );
+
+    let too_high = Save {
+        name: String::new(),
+        cache: Cache::default(),
+        loaded: None,
+        level: 300,
+        items: Vec::new(),
+        rank: 0,
+    };
+
+    assert_eq!(
+        too_high.to_bb().unwrap_err().to_string(),
+        "Invalid state: level doesn't fit into a byte"
+    );
+
+    let message = Message::Chat("hi".into(), Cache(vec![1]));
+    let bytes = message.to_bb().unwrap().into_vec();
+
+    assert_eq!(bytes, [0, 2, b'h', b'i']);
+    assert_eq!(
+        Message::from_bytes_exact(&bytes).unwrap(),
+        Message::Chat("hi".into(), Cache::default())
+    );
+}
+
+#[test]
+fn test_enum_ids() {
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    enum Unit {
+        A,
+        B = 5,
+        C,
+        #[serbytes(id = 2)]
+        D,
+    }
+
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    enum Data {
+        #[serbytes(id = 200)]
+        Removed,
+        Kept(u8),
+        #[serbytes(id = 7)]
+        Named {
+            value: u16,
+        },
+    }
+
+    for (value, id) in [(Unit::A, 0), (Unit::B, 5), (Unit::C, 6), (Unit::D, 2)] {
+        let bytes = value.to_bb().unwrap().into_vec();
+
+        assert_eq!(bytes, [id]);
+        assert_eq!(Unit::from_bytes_exact(&bytes).unwrap(), value);
+    }
+
+    for (value, id) in [
+        (Data::Removed, 200),
+        (Data::Kept(3), 201),
+        (Data::Named { value: 4 }, 7),
+    ] {
+        let bytes = value.to_bb().unwrap().into_vec();
+
+        assert_eq!(bytes[0], id);
+        assert_eq!(Data::from_bytes_exact(&bytes).unwrap(), value);
+    }
+
+    // Gaps between the ids aren't valid variants
+    let err = Unit::from_bytes(&[1]).unwrap_err();
+    assert_eq!(
+        err.specific_error,
+        SpecificError::EnumOrdinalOutOfBounds {
+            max_bound: 6,
+            got: 1
+        }
+    );
+}
+
+#[test]
+fn test_enum_tags() {
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    #[serbytes(tag = u16)]
+    enum Wide {
+        A,
+        #[serbytes(id = 1000)]
+        B(u8),
+    }
+
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    #[serbytes(tag = varint)]
+    enum Varint {
+        A,
+        #[serbytes(id = 300)]
+        B {
+            value: u8,
+        },
+    }
+
+    #[derive(SerBytes, Debug, Copy, Clone, Eq, PartialEq)]
+    #[serbytes(tag = bits)]
+    enum State {
+        Idle,
+        Walking,
+        Running,
+        Jumping,
+        Falling,
+    }
+
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    #[serbytes(tag = bits)]
+    enum Single {
+        Only(u8),
+    }
+
+    assert_eq!(Wide::B(7).to_bb().unwrap().into_vec(), [0x03, 0xE8, 7]);
+    assert_eq!(Wide::from_bytes(&[0x03, 0xE8, 7]).unwrap(), Wide::B(7));
+    assert_eq!(Wide::size_hint(), 2);
+
+    let err = Wide::from_bytes(&[0x03, 0xE9]).unwrap_err();
+    assert_eq!(
+        err.specific_error,
+        SpecificError::EnumOrdinalOutOfBounds {
+            max_bound: 1000,
+            got: 1001
+        }
+    );
+
+    assert_eq!(Varint::A.to_bb().unwrap().into_vec(), [0]);
+    let bytes = Varint::B { value: 1 }.to_bb().unwrap().into_vec();
+    assert_eq!(bytes.len(), 3);
+    assert_eq!(
+        Varint::from_bytes_exact(&bytes).unwrap(),
+        Varint::B { value: 1 }
+    );
+
+    // 5 variants take up 3 bits each, so 8 states fit into 3 bytes
+    let states = [
+        State::Idle,
+        State::Walking,
+        State::Running,
+        State::Jumping,
+        State::Falling,
+        State::Running,
+        State::Idle,
+        State::Falling,
+    ];
+
+    let mut wbb = WriteByteBufferOwned::new().with_packing(Packing::Bitstream);
+    for state in states {
+        state.to_buf(&mut wbb);
+    }
+    let bytes = wbb.into_vec();
+    assert_eq!(bytes.len(), 3);
+    assert_eq!(State::size_hint(), 1);
+
+    let mut rbb = ReadByteBufferSlice::new(&bytes).with_packing(Packing::Bitstream);
+    for state in states {
+        assert_eq!(State::from_buf(&mut rbb).unwrap(), state);
+    }
+
+    // 3 bits can hold ids up to 7, which aren't all variants
+    let err = State::from_buf(
+        &mut ReadByteBufferSlice::new(&[0b1110_0000]).with_packing(Packing::Bitstream),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.specific_error,
+        SpecificError::EnumOrdinalOutOfBounds {
+            max_bound: 4,
+            got: 7
+        }
+    );
+
+    // A single variant needs no tag at all
+    assert_eq!(Single::Only(9).to_bb().unwrap().into_vec(), [9]);
+    assert_eq!(Single::from_bytes_exact(&[9]).unwrap(), Single::Only(9));
+}
+
+#[test]
+fn test_unknown_variants() {
+    // An older build of `Event`, which doesn't know about `Trade` or `Emote`
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    #[serbytes(delimited)]
+    enum OldEvent {
+        Join(String),
+        Leave,
+        #[serbytes(other)]
+        Unknown {
+            tag: u8,
+            bytes: Vec<u8>,
+        },
+    }
+
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    #[serbytes(delimited)]
+    enum Event {
+        Join(String),
+        Leave,
+        Trade { item: u32, count: u16 },
+        Emote(u8),
+    }
+
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    struct Log {
+        events: Vec<OldEvent>,
+        end: u8,
+    }
+
+    let events = vec![
+        Event::Join("a".into()),
+        Event::Trade { item: 7, count: 2 },
+        Event::Leave,
+        Event::Emote(5),
+    ];
+
+    let mut wbb = WriteByteBufferOwned::new();
+    events.to_buf(&mut wbb);
+    9u8.to_buf(&mut wbb);
+    let bytes = wbb.into_vec();
+
+    // The unknown variants are skipped over, so what follows them is still read correctly
+    let log = Log::from_bytes_exact(&bytes).unwrap();
+    assert_eq!(
+        log,
+        Log {
+            events: vec![
+                OldEvent::Join("a".into()),
+                OldEvent::Unknown {
+                    tag: 2,
+                    bytes: vec![0, 0, 0, 7, 0, 2],
+                },
+                OldEvent::Leave,
+                OldEvent::Unknown {
+                    tag: 3,
+                    bytes: vec![5],
+                },
+            ],
+            end: 9,
+        }
+    );
+
+    // Written back unchanged, so the newer build reads them again
+    let rewritten = log.to_bb().unwrap().into_vec();
+    assert_eq!(rewritten, bytes);
+    assert_eq!(Vec::<Event>::from_bytes(&rewritten).unwrap(), events);
+
+    // Every variant's payload is prefixed by its length
+    assert_eq!(Event::Emote(5).to_bb().unwrap().into_vec(), [3, 0, 1, 5]);
+    assert_eq!(Event::size_hint(), 1 + 2);
+
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    #[serbytes(tag = u16, delimited = u8)]
+    enum Small {
+        Known,
+        #[serbytes(other)]
+        Other(u16, Vec<u8>),
+    }
+
+    let bytes = [0x01, 0x00, 2, 0xAB, 0xCD];
+    let small = Small::from_bytes_exact(&bytes).unwrap();
+    assert_eq!(small, Small::Other(256, vec![0xAB, 0xCD]));
+    assert_eq!(small.to_bb().unwrap().into_vec(), bytes);
+    assert_eq!(small.approx_size(), bytes.len());
+
+    // An unknown id too large for the `other` variant's id
+    #[derive(SerBytes, Debug)]
+    #[serbytes(tag = u16, delimited)]
+    enum Narrow {
+        Known,
+        #[serbytes(other)]
+        Unknown(u8, Vec<u8>),
+    }
+
+    let mut bytes = 300u16.to_bb().unwrap().into_vec();
+    bytes.extend(0u16.to_bb().unwrap().into_vec());
+
+    let err = Narrow::from_bytes(&bytes).unwrap_err();
+    assert_eq!(
+        err.specific_error,
+        SpecificError::EnumOrdinalOutOfBounds {
+            max_bound: 255,
+            got: 300
+        }
+    );
+    assert!(matches!(
+        Narrow::from_bytes(&[0, 0, 0, 0]).unwrap(),
+        Narrow::Known
+    ));
+}
+
+#[test]
+fn test_versioned_fields() {
+    fn unnamed() -> String {
+        "unnamed".into()
+    }
+
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    struct ConfigV1 {
+        id: u32,
+        legacy: u8,
+    }
+
+    #[derive(SerBytes)]
+    struct ConfigV3 {
+        id: u32,
+        name: String,
+        retries: Var<u16>,
+    }
+
+    #[derive(SerBytes, Debug, Eq, PartialEq)]
+    #[serbytes(version = 3)]
+    struct Config {
+        id: u32,
+        #[serbytes(until = 2)]
+        legacy: u8,
+        #[serbytes(since = 2, default = "unnamed")]
+        name: String,
+        #[serbytes(since = 3)]
+        retries: Var<u16>,
+    }
+
+    let config = Config {
+        id: 7,
+        legacy: 0,
+        name: "config".into(),
+        retries: Var(5),
+    };
+
+    // The removed field isn't written
+    let bytes = config.to_bb().unwrap().into_vec();
+    assert_eq!(
+        bytes,
+        ConfigV3 {
+            id: 7,
+            name: "config".into(),
+            retries: Var(5),
+        }
+        .to_bb()
+        .unwrap()
+        .into_vec()
+    );
+    assert_eq!(Config::from_bytes(&bytes).unwrap(), config);
+    assert_eq!(config.approx_size(), bytes.len());
+
+    // Version 1 data still has the removed field, and predates the added ones
+    let v1_bytes = ConfigV1 { id: 7, legacy: 9 }.to_bb().unwrap().into_vec();
+    let mut rbb = ReadByteBufferSlice::new(&v1_bytes);
+    assert_eq!(
+        Config::from_buf_versioned(&mut rbb, 1).unwrap(),
+        Config {
+            id: 7,
+            legacy: 9,
+            name: unnamed(),
+            retries: Var(0),
+        }
+    );
+    rbb.ensure_consumed().unwrap();
+
+    // The version comes from a header written before the data
+    let mut versioned_v1 = Var(1u32).to_bb().unwrap().into_vec();
+    versioned_v1.extend(&v1_bytes);
+    let read = Versioned::<Config>::from_bytes_exact(&versioned_v1).unwrap();
+    assert_eq!(read.version_read(), 1);
+    assert!(read.did_update());
+    assert_eq!(read.inner.name, unnamed());
+
+    let versioned = Versioned::new(config);
+    let versioned_bytes = versioned.to_bb().unwrap().into_vec();
+    assert_eq!(versioned_bytes[0], 3);
+    assert_eq!(Versioned::from_bytes(&versioned_bytes).unwrap(), versioned);
+
+    // Data from a newer version can't be read
+    let mut newer = versioned_bytes.clone();
+    newer[0] = 4;
+    let err = Versioned::<Config>::from_bytes(&newer).unwrap_err();
+    assert_eq!(
+        err.specific_error,
+        SpecificError::UnknownVersion { current: 3, got: 4 }
+    );
+
+    // Or from the `CurrentVersion` of a `VersioningWrapper`
+    #[derive(SerBytes, Copy, Clone)]
+    struct ConfigVersion(u16);
+
+    impl CurrentVersion for ConfigVersion {
+        type Output = Config;
+
+        fn get_data_from_buf<R>(&self, buf: &mut R) -> BBReadResult<Self::Output>
+        where
+            R: ReadByteBuffer + ?Sized,
+        {
+            Config::from_buf_versioned(buf, self.0.into())
+        }
+
+        fn current_version() -> Self {
+            Self(Config::CURRENT_VERSION as u16)
+        }
+    }
+
+    let mut wrapped_v1 = 1u16.to_bb().unwrap().into_vec();
+    wrapped_v1.extend(&v1_bytes);
+    let read = VersioningWrapper::<Config, ConfigVersion>::from_bytes_exact(&wrapped_v1).unwrap();
+    assert_eq!(read.inner.legacy, 9);
+    assert_eq!(read.inner.retries, Var(0));
+}